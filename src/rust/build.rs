@@ -5,8 +5,17 @@ use std::process::{Command, Stdio};
 
 fn main() {
     let out_dir = env::var("OUT_DIR").unwrap();
-    // FIXME: maybe pyo3-build-config should provide a way to do this?
-    let python = env::var("PYO3_PYTHON").unwrap_or_else(|_| "python3".to_string());
+    // `build_openssl.py` is host-side codegen (it just emits a `.c` file) and
+    // must run with a Python that's actually executable on this machine. When
+    // cross-compiling, pyo3-build-config's interpreter isn't runnable here (it
+    // describes the target), so we fall back to a host interpreter in that case.
+    let python_config = pyo3_build_config::get();
+    let python = match &python_config.executable {
+        Some(executable) if !is_cross_compiling() => executable.clone(),
+        _ => env::var("PYO3_PYTHON")
+            .unwrap_or_else(|_| "python3".to_string())
+            .into(),
+    };
     println!("cargo:rerun-if-changed=../_cffi_src/");
     let python_path = match env::var("PYTHONPATH") {
         Ok(mut val) => {
@@ -24,22 +33,37 @@ fn main() {
     if !output.status.success() {
         panic!(
             "failed to run build_openssl.py, stdout: \n{}\nstderr: \n{}\n",
-            String::from_utf8(output.stdout).unwrap(),
-            String::from_utf8(output.stderr).unwrap()
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
         );
     }
 
-    let stdout = String::from_utf8(output.stdout).unwrap();
+    // build_openssl.py emits `cargo:` directives on stdout alongside its other
+    // (non-UTF-8-safe) diagnostic output, so decode it lossily rather than
+    // failing the build over something outside the `cargo:` lines we care about.
+    let stdout = String::from_utf8_lossy(&output.stdout);
     for line in stdout.lines() {
         if line.starts_with("cargo:") {
             println!("{}", line);
         }
     }
-    let python_include = run_python_script(
-        &python,
-        "import sysconfig; print(sysconfig.get_path('include'), end='')",
-    )
-    .unwrap();
+
+    // The include path needs to match the *target* Python. When cross-compiling,
+    // the host interpreter's `sysconfig` can't tell us that, so we require it to
+    // be supplied explicitly (mirroring how `PYO3_CROSS_LIB_DIR` is used to point
+    // pyo3 itself at the target's Python libs/headers).
+    let python_include = if is_cross_compiling() {
+        env::var("PYO3_CROSS_INCLUDE_DIR").expect(
+            "PYO3_CROSS_INCLUDE_DIR must be set to the target Python's include directory \
+             when cross-compiling",
+        )
+    } else {
+        run_python_script(
+            &python,
+            "import sysconfig; print(sysconfig.get_path('include'), end='')",
+        )
+        .unwrap()
+    };
     let openssl_include =
         std::env::var_os("DEP_OPENSSL_INCLUDE").expect("unable to find openssl include path");
     let openssl_c = Path::new(&out_dir).join("_openssl.c");
@@ -50,6 +74,15 @@ fn main() {
         .compile("_openssl.a");
 }
 
+/// Returns true if we're building for a target other than the host we're
+/// running on (e.g. via `cargo build --target ...`).
+fn is_cross_compiling() -> bool {
+    match (env::var("HOST"), env::var("TARGET")) {
+        (Ok(host), Ok(target)) => host != target,
+        _ => false,
+    }
+}
+
 /// Run a python script using the specified interpreter binary.
 fn run_python_script(interpreter: impl AsRef<Path>, script: &str) -> Result<String, String> {
     let interpreter = interpreter.as_ref();
@@ -76,10 +109,8 @@ fn run_python_script(interpreter: impl AsRef<Path>, script: &str) -> Result<Stri
         )),
         Ok(ok) if !ok.status.success() => Err(format!(
             "Python script failed: {}",
-            String::from_utf8(ok.stderr).expect("failed to parse Python script output as utf-8")
+            String::from_utf8_lossy(&ok.stderr)
         )),
-        Ok(ok) => Ok(
-            String::from_utf8(ok.stdout).expect("failed to parse Python script output as utf-8")
-        ),
+        Ok(ok) => Ok(String::from_utf8_lossy(&ok.stdout).into_owned()),
     }
 }
@@ -0,0 +1,127 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! RFC 6698 DANE/TLSA certificate-association verification.
+//!
+//! This is an alternative (or supplementary) trust model to [`crate::policy`]:
+//! instead of (or in addition to) building a path to a trusted root, a
+//! caller can authenticate a certificate against a `TLSA` record fetched
+//! from DNS. `matches_tlsa` only checks the association itself; for the
+//! certificate usages that also require a valid PKIX path (`PKIX-TA` and
+//! `PKIX-EE`), the caller is responsible for separately validating the
+//! chain with [`crate::policy::Policy`] and combining the two results.
+
+use cryptography_x509::certificate::Certificate;
+
+use crate::ops::CryptoOps;
+use crate::ValidationError;
+
+/// `CertificateUsage`, RFC 6698 §2.1.1.
+const CERT_USAGE_PKIX_TA: u8 = 0;
+const CERT_USAGE_PKIX_EE: u8 = 1;
+const CERT_USAGE_DANE_TA: u8 = 2;
+const CERT_USAGE_DANE_EE: u8 = 3;
+
+/// `Selector`, RFC 6698 §2.1.2.
+const SELECTOR_FULL_CERTIFICATE: u8 = 0;
+const SELECTOR_SUBJECT_PUBLIC_KEY_INFO: u8 = 1;
+
+/// `MatchingType`, RFC 6698 §2.1.3.
+const MATCHING_TYPE_EXACT: u8 = 0;
+const MATCHING_TYPE_SHA256: u8 = 1;
+const MATCHING_TYPE_SHA512: u8 = 2;
+
+/// A parsed `TLSA` resource record (RFC 6698 §2.1).
+pub struct TlsaRecord {
+    pub cert_usage: u8,
+    pub selector: u8,
+    pub matching_type: u8,
+    pub association_data: Vec<u8>,
+}
+
+/// Verifies certificates against [`TlsaRecord`]s.
+pub struct DaneVerifier<B: CryptoOps> {
+    pub ops: B,
+}
+
+impl<B: CryptoOps> DaneVerifier<B> {
+    pub fn new(ops: B) -> Self {
+        Self { ops }
+    }
+
+    /// Checks whether `chain` satisfies `tlsa`.
+    ///
+    /// `chain` is ordered leaf-first, as presented by the peer. Depending
+    /// on `tlsa.cert_usage`, only part of `chain` is actually searched for
+    /// a matching association:
+    ///
+    /// * DANE-EE (3) and PKIX-EE (1) only ever match the leaf
+    ///   (`chain[0]`); DANE-EE additionally bypasses path building
+    ///   entirely (the association is the sole basis of trust), while
+    ///   PKIX-EE requires the caller to separately confirm `chain` builds
+    ///   a valid PKIX path.
+    /// * DANE-TA (2) and PKIX-TA (0) match any certificate in `chain`
+    ///   (the matched certificate is meant to anchor trust, whether as a
+    ///   self-issued trust anchor or an intermediate); PKIX-TA
+    ///   additionally requires the caller to confirm that the matched
+    ///   certificate was actually used to build a valid PKIX path.
+    pub fn matches_tlsa(
+        &self,
+        chain: &[Certificate<'_>],
+        tlsa: &TlsaRecord,
+    ) -> Result<bool, ValidationError> {
+        let candidates: &[Certificate<'_>] = match tlsa.cert_usage {
+            CERT_USAGE_PKIX_EE | CERT_USAGE_DANE_EE => match chain.first() {
+                Some(leaf) => std::slice::from_ref(leaf),
+                None => return Ok(false),
+            },
+            CERT_USAGE_PKIX_TA | CERT_USAGE_DANE_TA => chain,
+            _ => {
+                return Err(ValidationError::Other(format!(
+                    "unsupported TLSA certificate usage: {}",
+                    tlsa.cert_usage
+                )))
+            }
+        };
+
+        for cert in candidates {
+            if self.matches_association(cert, tlsa)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn matches_association(
+        &self,
+        cert: &Certificate<'_>,
+        tlsa: &TlsaRecord,
+    ) -> Result<bool, ValidationError> {
+        let selected = match tlsa.selector {
+            SELECTOR_FULL_CERTIFICATE => asn1::write_single(cert)
+                .map_err(|_| ValidationError::Other("failed to re-encode certificate".to_string())),
+            SELECTOR_SUBJECT_PUBLIC_KEY_INFO => asn1::write_single(&cert.tbs_cert.spki)
+                .map_err(|_| ValidationError::Other("failed to re-encode SPKI".to_string())),
+            _ => Err(ValidationError::Other(format!(
+                "unsupported TLSA selector: {}",
+                tlsa.selector
+            ))),
+        }?;
+
+        let matched = match tlsa.matching_type {
+            MATCHING_TYPE_EXACT => selected == tlsa.association_data,
+            MATCHING_TYPE_SHA256 => self.ops.sha256(&selected).as_slice() == tlsa.association_data,
+            MATCHING_TYPE_SHA512 => self.ops.sha512(&selected).as_slice() == tlsa.association_data,
+            _ => {
+                return Err(ValidationError::Other(format!(
+                    "unsupported TLSA matching type: {}",
+                    tlsa.matching_type
+                )))
+            }
+        };
+
+        Ok(matched)
+    }
+}
@@ -5,6 +5,8 @@
 #![forbid(unsafe_code)]
 
 pub mod certificate;
+pub mod dane;
+pub mod dnssec_chain;
 pub mod ops;
 pub mod policy;
 pub mod trust_store;
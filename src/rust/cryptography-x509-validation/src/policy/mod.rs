@@ -3,7 +3,13 @@
 // for complete details.
 
 mod extension;
+mod name_constraints;
+mod policy_tree;
+mod profile;
+mod resources;
+mod rpki;
 
+use std::cell::RefCell;
 use std::collections::HashSet;
 
 use asn1::ObjectIdentifier;
@@ -11,22 +17,29 @@ use cryptography_x509::certificate::Certificate;
 use once_cell::sync::Lazy;
 
 use cryptography_x509::common::{
-    AlgorithmIdentifier, AlgorithmParameters, EcParameters, RsaPssParameters, Time,
-    PSS_SHA256_HASH_ALG, PSS_SHA256_MASK_GEN_ALG, PSS_SHA384_HASH_ALG, PSS_SHA384_MASK_GEN_ALG,
-    PSS_SHA512_HASH_ALG, PSS_SHA512_MASK_GEN_ALG,
+    AlgorithmIdentifier, AlgorithmParameters, EcParameters, RsaPssParameters,
+    SubjectPublicKeyInfo, Time, PSS_SHA256_HASH_ALG, PSS_SHA256_MASK_GEN_ALG, PSS_SHA384_HASH_ALG,
+    PSS_SHA384_MASK_GEN_ALG, PSS_SHA512_HASH_ALG, PSS_SHA512_MASK_GEN_ALG,
 };
 use cryptography_x509::extensions::{
-    BasicConstraints, Extensions, KeyUsage, SubjectAlternativeName,
+    BasicConstraints, CertificatePolicies, Extensions, InhibitAnyPolicy, KeyUsage,
+    NameConstraints as NameConstraintsExtension, PolicyConstraints, PolicyMappings,
+    SubjectAlternativeName,
 };
 use cryptography_x509::name::GeneralName;
 use cryptography_x509::oid::{
-    AUTHORITY_INFORMATION_ACCESS_OID, AUTHORITY_KEY_IDENTIFIER_OID, BASIC_CONSTRAINTS_OID,
-    EC_SECP256R1, EC_SECP384R1, EC_SECP521R1, EKU_SERVER_AUTH_OID, EXTENDED_KEY_USAGE_OID,
-    KEY_USAGE_OID, NAME_CONSTRAINTS_OID, POLICY_CONSTRAINTS_OID, SUBJECT_ALTERNATIVE_NAME_OID,
-    SUBJECT_DIRECTORY_ATTRIBUTES_OID, SUBJECT_KEY_IDENTIFIER_OID,
+    ANY_POLICY_OID, BASIC_CONSTRAINTS_OID, CERTIFICATE_POLICIES_OID, EC_SECP256R1, EC_SECP384R1,
+    EC_SECP521R1, INHIBIT_ANY_POLICY_OID, KEY_USAGE_OID, NAME_CONSTRAINTS_OID,
+    POLICY_CONSTRAINTS_OID, POLICY_MAPPINGS_OID, SUBJECT_ALTERNATIVE_NAME_OID,
 };
 
-use self::extension::{ca, common, ee, Criticality, ExtensionPolicy};
+use self::extension::ExtensionPolicy;
+use self::name_constraints::NameConstraints;
+use self::policy_tree::PolicyState;
+pub use self::profile::{Profile, Smime, WebPkiClientAuth, WebPkiServerAuth};
+pub use self::rpki::RpkiCertificate;
+use self::resources::EffectiveResources;
+use self::rpki::{SBGP_AUTONOMOUS_SYS_NUM_OID, SBGP_IP_ADDR_BLOCK_OID};
 use crate::ops::CryptoOps;
 use crate::types::{DNSName, DNSPattern, IPAddress};
 use crate::ValidationError;
@@ -57,10 +70,42 @@ static SPKI_SECP521R1: AlgorithmIdentifier<'_> = AlgorithmIdentifier {
     params: AlgorithmParameters::Ec(EcParameters::NamedCurve(EC_SECP521R1)),
 };
 
+// Ed25519 (RFC 8410): the same AlgorithmIdentifier is used for both SPKI
+// and signatures, since EdDSA has no separate signature parameters.
+static SPKI_ED25519: AlgorithmIdentifier<'_> = AlgorithmIdentifier {
+    oid: asn1::DefinedByMarker::marker(),
+    params: AlgorithmParameters::Ed25519,
+};
+
+// Ed448 (RFC 8410): same caveat as `SPKI_ED25519`.
+static SPKI_ED448: AlgorithmIdentifier<'_> = AlgorithmIdentifier {
+    oid: asn1::DefinedByMarker::marker(),
+    params: AlgorithmParameters::Ed448,
+};
+
 /// Permitted algorithms, from CA/B Forum's Baseline Requirements, section 7.1.3.1 (page 96)
 /// https://cabforum.org/wp-content/uploads/CA-Browser-Forum-BR-v2.0.0.pdf
-pub static WEBPKI_PERMITTED_SPKI_ALGORITHMS: Lazy<HashSet<&AlgorithmIdentifier<'_>>> =
-    Lazy::new(|| HashSet::from([&SPKI_RSA, &SPKI_SECP256R1, &SPKI_SECP384R1, &SPKI_SECP521R1]));
+pub static WEBPKI_PERMITTED_SPKI_ALGORITHMS: Lazy<HashSet<&AlgorithmIdentifier<'_>>> = Lazy::new(|| {
+    HashSet::from([
+        &SPKI_RSA,
+        &SPKI_SECP256R1,
+        &SPKI_SECP384R1,
+        &SPKI_SECP521R1,
+        // EdDSA (RFC 8410) is not among the CA/B 7.1.3.1 algorithms; it is
+        // permitted here in addition to them.
+        &SPKI_ED25519,
+        &SPKI_ED448,
+    ])
+});
+
+/// An alternative permitted SPKI algorithm set for EdDSA-based chains
+/// (RFC 8410), for profiles outside the Web PKI baseline -- e.g. an
+/// internal PKI built entirely on Ed25519/Ed448. Not used by
+/// [`WebPkiServerAuth`]/[`WebPkiClientAuth`]; a [`Profile`] that wants it
+/// overrides `permitted_public_key_algorithms`/`permitted_signature_algorithms`
+/// to return it instead of the `WEBPKI_PERMITTED_*` defaults.
+pub static EDDSA_PERMITTED_SPKI_ALGORITHMS: Lazy<HashSet<&AlgorithmIdentifier<'_>>> =
+    Lazy::new(|| HashSet::from([&SPKI_ED25519, &SPKI_ED448]));
 
 // Signature AlgorithmIdentifier constants, as defined in CA/B 7.1.3.2.
 
@@ -147,9 +192,17 @@ pub static WEBPKI_PERMITTED_SIGNATURE_ALGORITHMS: Lazy<HashSet<&AlgorithmIdentif
             &ECDSA_SHA256,
             &ECDSA_SHA384,
             &ECDSA_SHA512,
+            // EdDSA (RFC 8410) is not among the CA/B 7.1.3.2 algorithms; it
+            // is permitted here in addition to them.
+            &SPKI_ED25519,
+            &SPKI_ED448,
         ])
     });
 
+/// The EdDSA counterpart to [`EDDSA_PERMITTED_SPKI_ALGORITHMS`].
+pub static EDDSA_PERMITTED_SIGNATURE_ALGORITHMS: Lazy<HashSet<&AlgorithmIdentifier<'_>>> =
+    Lazy::new(|| HashSet::from([&SPKI_ED25519, &SPKI_ED448]));
+
 /// A default reasonable maximum chain depth.
 ///
 /// This depth was chosen to balance between common validation lengths
@@ -161,6 +214,9 @@ pub static WEBPKI_PERMITTED_SIGNATURE_ALGORITHMS: Lazy<HashSet<&AlgorithmIdentif
 /// necessary.
 const DEFAULT_MAX_CHAIN_DEPTH: u8 = 8;
 
+/// The default minimum RSA modulus size, in bits, per CA/B BR §6.1.5.
+const DEFAULT_MIN_RSA_MODULUS_BITS: u32 = 2048;
+
 /// Represents a logical certificate "subject," i.e. a principal matching
 /// one of the names listed in a certificate's `subjectAltNames` extension.
 pub enum Subject<'a> {
@@ -189,7 +245,13 @@ impl Subject<'_> {
 }
 
 /// A `Policy` describes user-configurable aspects of X.509 path validation.
-pub struct Policy<'a, B: CryptoOps> {
+///
+/// `Policy` is generic over a [`Profile`], which supplies the parts of
+/// validation that vary by what kind of chain is being built (the leaf's
+/// required EKU, the permitted algorithm sets, and the common/CA/EE
+/// extension policies). It defaults to [`WebPkiServerAuth`], i.e. the CA/B
+/// Forum's Baseline Requirements profile for TLS server certificates.
+pub struct Policy<'a, B: CryptoOps, P: Profile<B> = WebPkiServerAuth> {
     pub ops: B,
 
     /// A top-level constraint on the length of intermediate CA paths
@@ -207,129 +269,96 @@ pub struct Policy<'a, B: CryptoOps> {
     /// be valid at this time.
     pub validation_time: asn1::DateTime,
 
-    /// An extended key usage that must appear in EEs validated by this policy.
-    pub extended_key_usage: ObjectIdentifier,
+    /// A tolerance, in seconds, applied to either side of each certificate's
+    /// validity window before comparing it against `validation_time`.
+    /// Defaults to zero (strict CA/B behavior); embedders validating against
+    /// a clock that may be a little off can widen this instead of fudging
+    /// `validation_time` itself.
+    pub clock_skew_seconds: u64,
+
+    /// An extended key usage that must appear in EEs validated by this
+    /// policy, if the profile requires one at all (see
+    /// [`Profile::extended_key_usage`]).
+    pub extended_key_usage: Option<ObjectIdentifier>,
 
     /// The set of permitted public key algorithms, identified by their
     /// algorithm identifiers.
+    ///
+    /// NOTE: this constrains EC keys to a fixed set of named curves (the
+    /// curve OID is part of the algorithm identifier itself), but can't
+    /// constrain RSA key strength: `rsaEncryption`'s algorithm identifier
+    /// doesn't carry a modulus size. See `min_rsa_modulus_bits` for that.
     pub permitted_public_key_algorithms: HashSet<AlgorithmIdentifier<'a>>,
 
+    /// The minimum RSA modulus size, in bits, permitted for an issuer's or
+    /// child's SPKI. Per CA/B BR §6.1.5, defaults to 2048.
+    pub min_rsa_modulus_bits: u32,
+
     /// The set of permitted signature algorithms, identified by their
     /// algorithm identifiers.
     pub permitted_signature_algorithms: HashSet<AlgorithmIdentifier<'a>>,
 
+    /// The set of policy OIDs the caller will accept as the chain's
+    /// overall certificate policy, per RFC 5280 §6.1.1(c) `user-initial-policy-set`.
+    /// Defaults to `{ anyPolicy }`, i.e. no particular policy is required.
+    pub initial_policy_set: HashSet<ObjectIdentifier>,
+
     common_extension_policies: Vec<ExtensionPolicy<B>>,
     ca_extension_policies: Vec<ExtensionPolicy<B>>,
     ee_extension_policies: Vec<ExtensionPolicy<B>>,
+
+    /// Name constraints accumulated from each non-self-issued CA in the
+    /// chain built so far, per RFC 5280 6.1.4(g)-(h). This is threaded
+    /// through `valid_issuer` rather than being recomputed from scratch,
+    /// since path validation walks the chain one edge at a time.
+    name_constraints: RefCell<NameConstraints<'a>>,
+
+    /// The valid-policy tree and associated RFC 5280 §6.1 state variables,
+    /// accumulated the same way as `name_constraints`.
+    policy_state: RefCell<PolicyState>,
+
+    /// The RFC 3779 effective resources (IP address blocks and AS numbers)
+    /// established so far by the chain built up to the current certificate.
+    /// Encompassment is checked under every profile; only chains whose
+    /// certificates carry the RFC 3779 extensions (as under
+    /// [`RpkiCertificate`]) are affected by it. `None` until the trust anchor's own resources have
+    /// been established, after which it always holds the most recently
+    /// validated certificate's effective resources.
+    resources: RefCell<Option<EffectiveResources>>,
+
+    _profile: std::marker::PhantomData<P>,
 }
 
-impl<'a, B: CryptoOps> Policy<'a, B> {
-    /// Create a new policy with defaults for the certificate profile defined in
-    /// the CA/B Forum's Basic Requirements.
+impl<'a, B: CryptoOps, P: Profile<B>> Policy<'a, B, P> {
+    /// Create a new policy, configured by `profile`, for the given
+    /// `subject`, `time`, and `max_chain_depth`.
     pub fn new(
         ops: B,
+        profile: P,
         subject: Subject<'a>,
         time: asn1::DateTime,
         max_chain_depth: Option<u8>,
+        min_rsa_modulus_bits: Option<u32>,
+        clock_skew_seconds: Option<u64>,
     ) -> Self {
         Self {
             ops,
             max_chain_depth: max_chain_depth.unwrap_or(DEFAULT_MAX_CHAIN_DEPTH),
             subject,
             validation_time: time,
-            extended_key_usage: EKU_SERVER_AUTH_OID.clone(),
-            permitted_public_key_algorithms: WEBPKI_PERMITTED_SPKI_ALGORITHMS
-                .clone()
-                .into_iter()
-                .cloned()
-                .collect(),
-            permitted_signature_algorithms: WEBPKI_PERMITTED_SIGNATURE_ALGORITHMS
-                .clone()
-                .into_iter()
-                .cloned()
-                .collect(),
-            common_extension_policies: Vec::from([
-                // 5280 4.2.1.8: Subject Directory Attributes
-                ExtensionPolicy::maybe_present(
-                    SUBJECT_DIRECTORY_ATTRIBUTES_OID,
-                    Criticality::NonCritical,
-                    None,
-                ),
-                // 5280 4.2.2.1: Authority Information Access
-                ExtensionPolicy::maybe_present(
-                    AUTHORITY_INFORMATION_ACCESS_OID,
-                    Criticality::NonCritical,
-                    Some(common::authority_information_access),
-                ),
-                // 5280 4.2.1.12: Extended Key Usage
-                //
-                // NOTE: CABF requires EKUs in all subscriber certs and in many
-                // non-root CA certs, but validators widely ignore this
-                // requirement and treat a missing EKU as "any EKU".
-                // We choose to be permissive here.
-                ExtensionPolicy::maybe_present(
-                    EXTENDED_KEY_USAGE_OID,
-                    Criticality::NonCritical,
-                    Some(common::extended_key_usage),
-                ),
-            ]),
-            ca_extension_policies: Vec::from([
-                // 5280 4.2.1.1: Authority Key Identifier
-                ExtensionPolicy::maybe_present(
-                    AUTHORITY_KEY_IDENTIFIER_OID,
-                    Criticality::NonCritical,
-                    Some(ca::authority_key_identifier),
-                ),
-                // 5280 4.2.1.2: Subject Key Identifier
-                // NOTE: CABF requires SKI in CA certificates, but many older CAs lack it.
-                // We choose to be permissive here.
-                ExtensionPolicy::maybe_present(
-                    SUBJECT_KEY_IDENTIFIER_OID,
-                    Criticality::NonCritical,
-                    None,
-                ),
-                // 5280 4.2.1.3: Key Usage
-                ExtensionPolicy::present(KEY_USAGE_OID, Criticality::Agnostic, Some(ca::key_usage)),
-                // 5280 4.2.1.9: Basic Constraints
-                ExtensionPolicy::present(
-                    BASIC_CONSTRAINTS_OID,
-                    Criticality::Critical,
-                    Some(ca::basic_constraints),
-                ),
-                // 5280 4.2.1.10: Name Constraints
-                // NOTE: MUST be critical in 5280, but CABF relaxes to MAY.
-                ExtensionPolicy::maybe_present(
-                    NAME_CONSTRAINTS_OID,
-                    Criticality::Agnostic,
-                    Some(ca::name_constraints),
-                ),
-                // 5280 4.2.1.10: Policy Constraints
-                ExtensionPolicy::maybe_present(POLICY_CONSTRAINTS_OID, Criticality::Critical, None),
-            ]),
-            ee_extension_policies: Vec::from([
-                // 5280 4.2.1.1.: Authority Key Identifier
-                ExtensionPolicy::present(
-                    AUTHORITY_KEY_IDENTIFIER_OID,
-                    Criticality::NonCritical,
-                    None,
-                ),
-                // 5280 4.2.1.3: Key Usage
-                ExtensionPolicy::maybe_present(KEY_USAGE_OID, Criticality::Agnostic, None),
-                // CA/B 7.1.2.7.12 Subscriber Certificate Subject Alternative Name
-                ExtensionPolicy::present(
-                    SUBJECT_ALTERNATIVE_NAME_OID,
-                    Criticality::Agnostic,
-                    Some(ee::subject_alternative_name),
-                ),
-                // 5280 4.2.1.9: Basic Constraints
-                ExtensionPolicy::maybe_present(
-                    BASIC_CONSTRAINTS_OID,
-                    Criticality::Agnostic,
-                    Some(ee::basic_constraints),
-                ),
-                // 5280 4.2.1.10: Name Constraints
-                ExtensionPolicy::not_present(NAME_CONSTRAINTS_OID),
-            ]),
+            clock_skew_seconds: clock_skew_seconds.unwrap_or(0),
+            extended_key_usage: profile.extended_key_usage(),
+            permitted_public_key_algorithms: profile.permitted_public_key_algorithms(),
+            min_rsa_modulus_bits: min_rsa_modulus_bits.unwrap_or(DEFAULT_MIN_RSA_MODULUS_BITS),
+            permitted_signature_algorithms: profile.permitted_signature_algorithms(),
+            initial_policy_set: HashSet::from([ANY_POLICY_OID.clone()]),
+            common_extension_policies: profile.common_extension_policies(),
+            ca_extension_policies: profile.ca_extension_policies(),
+            ee_extension_policies: profile.ee_extension_policies(),
+            name_constraints: RefCell::new(NameConstraints::default()),
+            policy_state: RefCell::new(PolicyState::default()),
+            resources: RefCell::new(None),
+            _profile: std::marker::PhantomData,
         }
     }
 
@@ -389,7 +418,11 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
         let not_after = cert.tbs_cert.validity.not_after.as_datetime();
         permits_validity_date(&cert.tbs_cert.validity.not_before)?;
         permits_validity_date(&cert.tbs_cert.validity.not_after)?;
-        if &self.validation_time < not_before || &self.validation_time > not_after {
+        let validation_time_secs = datetime_to_unix_seconds(&self.validation_time);
+        let skew_secs = i64::try_from(self.clock_skew_seconds).unwrap_or(i64::MAX);
+        if validation_time_secs.saturating_add(skew_secs) < datetime_to_unix_seconds(not_before)
+            || validation_time_secs.saturating_sub(skew_secs) > datetime_to_unix_seconds(not_after)
+        {
             return Err(ValidationError::Other(
                 "cert is not valid at validation time".to_string(),
             ));
@@ -503,6 +536,78 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
             ext_policy.permits(self, cert, extensions)?;
         }
 
+        // 5280 6.1.4(g)-(h) / 4.2.1.10: the EE's names must satisfy every
+        // name constraint accumulated from the chain's CAs.
+        let name_constraints = self.name_constraints.borrow();
+        name_constraints.permits(&GeneralName::DirectoryName(cert.subject()))?;
+        if let Some(san) = extensions.get_extension(&SUBJECT_ALTERNATIVE_NAME_OID) {
+            let san: SubjectAlternativeName<'_> = san.value()?;
+            for name in san.clone() {
+                name_constraints.permits(&name)?;
+            }
+        }
+
+        // 5280 6.1.3/6.1.5: fold the EE's own certificate policies into the
+        // valid-policy tree, then check the result against the caller's
+        // required policy set.
+        let certificate_policies = match extensions.get_extension(&CERTIFICATE_POLICIES_OID) {
+            Some(ext) => {
+                let cp: CertificatePolicies<'a> = ext.value()?;
+                Some(cp)
+            }
+            None => None,
+        };
+        let policy_mappings = match extensions.get_extension(&POLICY_MAPPINGS_OID) {
+            Some(ext) => {
+                let pm: PolicyMappings<'a> = ext.value()?;
+                Some(pm)
+            }
+            None => None,
+        };
+        let mut policy_state = self.policy_state.borrow_mut();
+        policy_state.process_leaf(certificate_policies.as_ref(), policy_mappings.as_ref())?;
+        policy_state.finish(&self.initial_policy_set)?;
+
+        // RFC 3779: if no issuer has been validated yet (i.e. this EE is
+        // self-issued and also the trust anchor), establish its effective
+        // resources directly from its own extensions. Otherwise its
+        // resources were already checked and recorded by `valid_issuer`.
+        if self.resources.borrow().is_none() {
+            let ip_addr_block = extensions
+                .get_extension(&SBGP_IP_ADDR_BLOCK_OID)
+                .map(|e| e.extn_value);
+            let autonomous_sys_num = extensions
+                .get_extension(&SBGP_AUTONOMOUS_SYS_NUM_OID)
+                .map(|e| e.extn_value);
+            if ip_addr_block.is_some() || autonomous_sys_num.is_some() {
+                *self.resources.borrow_mut() = Some(EffectiveResources::from_trust_anchor(
+                    ip_addr_block,
+                    autonomous_sys_num,
+                )?);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks `spki` against `min_rsa_modulus_bits`, if it's an RSA key.
+    /// Non-RSA keys (and RSA keys whose modulus size the backing
+    /// `CryptoOps` can't determine) are left unconstrained here, since
+    /// their strength is otherwise fully determined by
+    /// `permitted_public_key_algorithms` (e.g. a named EC curve).
+    fn check_rsa_modulus_strength(
+        &self,
+        spki: &SubjectPublicKeyInfo<'_>,
+    ) -> Result<(), ValidationError> {
+        if let Some(modulus_bits) = self.ops.rsa_modulus_bits(spki) {
+            if modulus_bits < self.min_rsa_modulus_bits {
+                return Err(ValidationError::Other(format!(
+                    "RSA modulus too small: {modulus_bits} bits, minimum is {}",
+                    self.min_rsa_modulus_bits
+                )));
+            }
+        }
+
         Ok(())
     }
 
@@ -529,6 +634,94 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
         // The issuer needs to be a valid CA at the current depth.
         self.permits_ca(issuer, current_depth, issuer_extensions)?;
 
+        // 5280 6.1.4(g)-(h): fold the issuer's name constraints into the
+        // running state, unless the issuer is self-issued (in which case
+        // its constraints don't apply to itself and carry no new
+        // information for the rest of the chain).
+        let issuer_self_issued = issuer.subject() == issuer.issuer();
+        if !issuer_self_issued {
+            if let Some(nc) = issuer_extensions.get_extension(&NAME_CONSTRAINTS_OID) {
+                let nc: NameConstraintsExtension<'a> = nc.value()?;
+                self.name_constraints.borrow_mut().accumulate(&nc)?;
+            }
+        }
+
+        // 5280 6.1.3-6.1.4: fold the issuer's certificate policies, policy
+        // mappings, and policy/inhibit-anyPolicy constraints into the
+        // running valid-policy tree state.
+        {
+            let certificate_policies = match issuer_extensions.get_extension(&CERTIFICATE_POLICIES_OID) {
+                Some(ext) => {
+                    let cp: CertificatePolicies<'a> = ext.value()?;
+                    Some(cp)
+                }
+                None => None,
+            };
+            let policy_mappings = match issuer_extensions.get_extension(&POLICY_MAPPINGS_OID) {
+                Some(ext) => {
+                    let pm: PolicyMappings<'a> = ext.value()?;
+                    Some(pm)
+                }
+                None => None,
+            };
+            let policy_constraints = match issuer_extensions.get_extension(&POLICY_CONSTRAINTS_OID) {
+                Some(ext) => {
+                    let pc: PolicyConstraints = ext.value()?;
+                    Some(pc)
+                }
+                None => None,
+            };
+            let inhibit_any_policy = match issuer_extensions.get_extension(&INHIBIT_ANY_POLICY_OID) {
+                Some(ext) => {
+                    let skip_certs: InhibitAnyPolicy = ext.value()?;
+                    Some(skip_certs)
+                }
+                None => None,
+            };
+
+            self.policy_state.borrow_mut().process_intermediate(
+                certificate_policies.as_ref(),
+                policy_mappings.as_ref(),
+                policy_constraints.as_ref(),
+                inhibit_any_policy,
+                issuer_self_issued,
+            )?;
+        }
+
+        // RFC 3779: check that `child`'s claimed IP/AS resources are
+        // encompassed by `issuer`'s effective resources, establishing
+        // `issuer`'s own effective resources from its extensions first if
+        // this is the first edge walked (i.e. `issuer` is the trust
+        // anchor).
+        {
+            let issuer_ip_addr_block = issuer_extensions
+                .get_extension(&SBGP_IP_ADDR_BLOCK_OID)
+                .map(|e| e.extn_value);
+            let issuer_autonomous_sys_num = issuer_extensions
+                .get_extension(&SBGP_AUTONOMOUS_SYS_NUM_OID)
+                .map(|e| e.extn_value);
+
+            let mut resources = self.resources.borrow_mut();
+            let issuer_resources = match resources.take() {
+                Some(r) => r,
+                None => EffectiveResources::from_trust_anchor(
+                    issuer_ip_addr_block,
+                    issuer_autonomous_sys_num,
+                )?,
+            };
+
+            let child_extensions = child.extensions()?;
+            let child_ip_addr_block = child_extensions
+                .get_extension(&SBGP_IP_ADDR_BLOCK_OID)
+                .map(|e| e.extn_value);
+            let child_autonomous_sys_num = child_extensions
+                .get_extension(&SBGP_AUTONOMOUS_SYS_NUM_OID)
+                .map(|e| e.extn_value);
+
+            *resources =
+                Some(issuer_resources.encompass(child_ip_addr_block, child_autonomous_sys_num)?);
+        }
+
         // CA/B 7.1.3.1 SubjectPublicKeyInfo
         if !self
             .permitted_public_key_algorithms
@@ -551,6 +744,14 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
             )));
         }
 
+        // CA/B BR §6.1.5: an allowed algorithm identifier isn't enough for
+        // RSA, since `rsaEncryption`'s identifier doesn't carry a modulus
+        // size. Check both ends of this edge, since `issuer`'s SPKI is only
+        // otherwise checked here if it was itself a `child` in an earlier
+        // call (i.e. it's never checked if it's the trust anchor).
+        self.check_rsa_modulus_strength(&issuer.tbs_cert.spki)?;
+        self.check_rsa_modulus_strength(&child.tbs_cert.spki)?;
+
         let pk = self
             .ops
             .public_key(issuer)
@@ -565,6 +766,23 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
     }
 }
 
+/// Converts a `DateTime` to seconds since the Unix epoch, so that a clock-
+/// skew tolerance can be applied with plain integer arithmetic instead of
+/// widening the `DateTime` values themselves (which `asn1::DateTime` has no
+/// API for). Uses Howard Hinnant's `days_from_civil` algorithm, valid for
+/// any date `DateTime` can represent.
+fn datetime_to_unix_seconds(dt: &asn1::DateTime) -> i64 {
+    let y = i64::from(dt.year()) - i64::from(dt.month() <= 2);
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (i64::from(dt.month()) + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + i64::from(dt.day()) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    days * 86_400 + i64::from(dt.hour()) * 3600 + i64::from(dt.minute()) * 60 + i64::from(dt.second())
+}
+
 fn permits_validity_date(validity_date: &Time) -> Result<(), ValidationError> {
     const GENERALIZED_DATE_CUTOFF_YEAR: u16 = 2050;
 
@@ -594,8 +812,8 @@ mod tests {
 
     use crate::{
         policy::{
-            Subject, SPKI_RSA, SPKI_SECP256R1, SPKI_SECP384R1, SPKI_SECP521R1,
-            WEBPKI_PERMITTED_SPKI_ALGORITHMS,
+            Subject, SPKI_ED25519, SPKI_ED448, SPKI_RSA, SPKI_SECP256R1, SPKI_SECP384R1,
+            SPKI_SECP521R1, WEBPKI_PERMITTED_SPKI_ALGORITHMS,
         },
         types::{DNSName, IPAddress},
     };
@@ -631,6 +849,18 @@ mod tests {
             let exp_encoding = b"0\x10\x06\x07*\x86H\xce=\x02\x01\x06\x05+\x81\x04\x00#";
             assert_eq!(asn1::write_single(&SPKI_SECP521R1).unwrap(), exp_encoding);
         }
+
+        {
+            assert!(WEBPKI_PERMITTED_SPKI_ALGORITHMS.contains(&SPKI_ED25519));
+            let exp_encoding = b"0\x05\x06\x03+ep";
+            assert_eq!(asn1::write_single(&SPKI_ED25519).unwrap(), exp_encoding);
+        }
+
+        {
+            assert!(WEBPKI_PERMITTED_SPKI_ALGORITHMS.contains(&SPKI_ED448));
+            let exp_encoding = b"0\x05\x06\x03+eq";
+            assert_eq!(asn1::write_single(&SPKI_ED448).unwrap(), exp_encoding);
+        }
     }
 
     #[test]
@@ -706,6 +936,20 @@ mod tests {
             let exp_encoding = b"0\n\x06\x08*\x86H\xce=\x04\x03\x04";
             assert_eq!(asn1::write_single(&ECDSA_SHA512).unwrap(), exp_encoding);
         }
+
+        // EdDSA (RFC 8410) signatures reuse the same `AlgorithmIdentifier`
+        // as their SPKI, since there are no separate signature parameters.
+        {
+            assert!(WEBPKI_PERMITTED_SIGNATURE_ALGORITHMS.contains(&SPKI_ED25519));
+            let exp_encoding = b"0\x05\x06\x03+ep";
+            assert_eq!(asn1::write_single(&SPKI_ED25519).unwrap(), exp_encoding);
+        }
+
+        {
+            assert!(WEBPKI_PERMITTED_SIGNATURE_ALGORITHMS.contains(&SPKI_ED448));
+            let exp_encoding = b"0\x05\x06\x03+eq";
+            assert_eq!(asn1::write_single(&SPKI_ED448).unwrap(), exp_encoding);
+        }
     }
 
     #[test]
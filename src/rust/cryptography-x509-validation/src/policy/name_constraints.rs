@@ -0,0 +1,348 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! RFC 5280 §4.2.1.10 name constraints, enforced as accumulated state across
+//! a certificate chain rather than certificate-by-certificate.
+//!
+//! A [`NameConstraints`] accumulates the `permittedSubtrees`/`excludedSubtrees`
+//! of every (non-self-issued) CA in a chain, from the trust anchor down to the
+//! leaf, and is finally consulted against the EE's asserted names.
+
+use cryptography_x509::extensions::NameConstraints as NameConstraintsExt;
+use cryptography_x509::name::GeneralName;
+
+use crate::types::{DNSName, IPAddress, IPConstraint, RFC822Constraint, RFC822Name};
+use crate::ValidationError;
+
+/// The kinds of `GeneralName` that RFC 5280 defines matching rules for.
+/// Other `GeneralName` variants are ignored by name constraints processing,
+/// per RFC 5280 §4.2.1.10.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum NameKind {
+    Dns,
+    Ip,
+    Rfc822,
+    DirectoryName,
+    Uri,
+}
+
+fn name_kind(name: &GeneralName<'_>) -> Option<NameKind> {
+    match name {
+        GeneralName::DNSName(_) => Some(NameKind::Dns),
+        GeneralName::IPAddress(_) => Some(NameKind::Ip),
+        GeneralName::RFC822Name(_) => Some(NameKind::Rfc822),
+        GeneralName::DirectoryName(_) => Some(NameKind::DirectoryName),
+        GeneralName::UniformResourceIdentifier(_) => Some(NameKind::Uri),
+        _ => None,
+    }
+}
+
+/// Accumulated permitted/excluded subtree state for a single `NameKind`.
+#[derive(Clone, Default)]
+struct SubtreeState<'a> {
+    /// Each entry is the `permittedSubtrees` list contributed by one CA in
+    /// the chain (for this `NameKind`); an empty outer `Vec` means
+    /// "unconstrained". A name must match at least one subtree *within*
+    /// each entry, so that a child CA's `permittedSubtrees` narrows rather
+    /// than widens whatever its issuer already permitted -- see `permits`.
+    permitted: Vec<Vec<GeneralName<'a>>>,
+    excluded: Vec<GeneralName<'a>>,
+}
+
+/// Accumulates name constraints across a certificate chain.
+///
+/// Per RFC 5280 §6.1.4(g)-(h), each non-self-issued CA's constraints are
+/// folded into the running state: `permittedSubtrees` intersect the existing
+/// permitted set (per name type), and `excludedSubtrees` are unioned into the
+/// excluded set.
+#[derive(Clone, Default)]
+pub(crate) struct NameConstraints<'a> {
+    dns: SubtreeState<'a>,
+    ip: SubtreeState<'a>,
+    rfc822: SubtreeState<'a>,
+    directory_name: SubtreeState<'a>,
+    uri: SubtreeState<'a>,
+}
+
+impl<'a> NameConstraints<'a> {
+    fn state_for(&mut self, kind: NameKind) -> &mut SubtreeState<'a> {
+        match kind {
+            NameKind::Dns => &mut self.dns,
+            NameKind::Ip => &mut self.ip,
+            NameKind::Rfc822 => &mut self.rfc822,
+            NameKind::DirectoryName => &mut self.directory_name,
+            NameKind::Uri => &mut self.uri,
+        }
+    }
+
+    fn state_for_ref(&self, kind: NameKind) -> &SubtreeState<'a> {
+        match kind {
+            NameKind::Dns => &self.dns,
+            NameKind::Ip => &self.ip,
+            NameKind::Rfc822 => &self.rfc822,
+            NameKind::DirectoryName => &self.directory_name,
+            NameKind::Uri => &self.uri,
+        }
+    }
+
+    /// Folds a CA's `NameConstraints` extension into the running state.
+    ///
+    /// Per RFC 5280 §6.1.4, this must be skipped for self-issued
+    /// intermediates (the caller is responsible for that check, since it
+    /// requires comparing issuer/subject and isn't specific to name
+    /// constraints).
+    pub(crate) fn accumulate(&mut self, nc: &NameConstraintsExt<'a>) -> Result<(), ValidationError> {
+        if let Some(excluded) = &nc.excluded_subtrees {
+            for subtree in excluded.clone() {
+                if let Some(kind) = name_kind(&subtree.base) {
+                    self.state_for(kind).excluded.push(subtree.base);
+                }
+            }
+        }
+
+        if let Some(permitted) = &nc.permitted_subtrees {
+            let mut by_kind: std::collections::HashMap<NameKind, Vec<GeneralName<'a>>> =
+                std::collections::HashMap::new();
+            for subtree in permitted.clone() {
+                if let Some(kind) = name_kind(&subtree.base) {
+                    by_kind.entry(kind).or_default().push(subtree.base);
+                }
+            }
+            for kind in [
+                NameKind::Dns,
+                NameKind::Ip,
+                NameKind::Rfc822,
+                NameKind::DirectoryName,
+                NameKind::Uri,
+            ] {
+                if let Some(new_permitted) = by_kind.remove(&kind) {
+                    // Each CA's `permittedSubtrees` is its own intersection
+                    // term: pushing it as a new entry (rather than merging
+                    // it into the existing one) is what makes `permits`
+                    // require a match against *every* CA's list, not just
+                    // the union of all of them.
+                    self.state_for(kind).permitted.push(new_permitted);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `Ok(())` if `name` is permitted by the accumulated state,
+    /// or a `ValidationError` describing the violation.
+    pub(crate) fn permits(&self, name: &GeneralName<'_>) -> Result<(), ValidationError> {
+        let Some(kind) = name_kind(name) else {
+            // Name types we don't have matching rules for are, per RFC 5280,
+            // not constrained.
+            return Ok(());
+        };
+        let state = self.state_for_ref(kind);
+
+        for excluded in &state.excluded {
+            if subtree_matches(excluded, name) {
+                return Err(ValidationError::Other(
+                    "name is explicitly excluded by name constraints".to_string(),
+                ));
+            }
+        }
+
+        // A name must match at least one subtree from *each* contributing
+        // CA's permitted list: intersection across the chain, union within
+        // a single CA's own list.
+        for subtrees in &state.permitted {
+            if !subtrees.iter().any(|base| subtree_matches(base, name)) {
+                return Err(ValidationError::Other(
+                    "name does not match any permitted subtree".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns true if `name` falls within the subtree rooted at `base`,
+/// using RFC 5280 §4.2.1.10's per-type subtree rules (DNS label suffix,
+/// IP address/mask, ...), not the wildcard/exact matching of
+/// `Subject::matches`.
+fn subtree_matches(base: &GeneralName<'_>, name: &GeneralName<'_>) -> bool {
+    match (base, name) {
+        (GeneralName::DNSName(base), GeneralName::DNSName(name)) => {
+            // A `dNSName` constraint is a domain suffix: `example.com`
+            // constrains `foo.example.com` and `example.com` itself.
+            match (DNSName::new(base.0), DNSName::new(name.0)) {
+                (Some(base), Some(name)) => dns_suffix_matches(&base, &name),
+                _ => false,
+            }
+        }
+        (GeneralName::IPAddress(base), GeneralName::IPAddress(name)) => {
+            // RFC 5280 §4.2.1.10: an `iPAddress` subtree is encoded in CIDR
+            // form (address followed by a network mask of the same width).
+            match (IPConstraint::from_bytes(base), IPAddress::from_bytes(name)) {
+                (Some(base), Some(name)) => base.matches(&name),
+                _ => false,
+            }
+        }
+        (GeneralName::DirectoryName(base), GeneralName::DirectoryName(name)) => {
+            // RFC 5280 §4.2.1.10: a `directoryName` constraint matches any
+            // name for which it's an initial prefix of RDNs. We don't have
+            // an RDN-by-RDN comparison available here, so we conservatively
+            // only recognize an exact match; this rejects some names a
+            // prefix match would permit, but never permits one it shouldn't.
+            base == name
+        }
+        (
+            GeneralName::UniformResourceIdentifier(base),
+            GeneralName::UniformResourceIdentifier(name),
+        ) => {
+            // A `uniformResourceIdentifier` constraint applies to the URI's
+            // host, matched the same way as a `dNSName` constraint.
+            match (uri_host(name.0), DNSName::new(base.0)) {
+                (Some(host), Some(base)) => match DNSName::new(host) {
+                    Some(host) => dns_suffix_matches(&base, &host),
+                    None => false,
+                },
+                _ => false,
+            }
+        }
+        (GeneralName::RFC822Name(base), GeneralName::RFC822Name(name)) => {
+            // RFC 5280 §4.2.1.10: a `rfc822Name` constraint is either a
+            // full mailbox, a bare host (exact-host match), or a
+            // `.`-prefixed host (strict-subdomain match).
+            match (RFC822Constraint::new(base.0), RFC822Name::new(name.0)) {
+                (Some(constraint), Some(name)) => constraint.matches(&name),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Returns true if `constraint` is a case-insensitive, right-anchored
+/// label suffix of `name` -- i.e. `name` is `constraint` itself, or some
+/// number of labels prepended to it. Walks up `name`'s ancestry via
+/// repeated [`DNSName::parent`] rather than any substring/string-suffix
+/// test, so that (e.g.) a constraint of `example.com` matches
+/// `host.example.com` but not `notexample.com`.
+fn dns_suffix_matches(constraint: &DNSName<'_>, name: &DNSName<'_>) -> bool {
+    if name == constraint {
+        return true;
+    }
+
+    let mut ancestor = name.parent();
+    while let Some(current) = ancestor {
+        if &current == constraint {
+            return true;
+        }
+        ancestor = current.parent();
+    }
+
+    false
+}
+
+/// Extracts the host portion of a URI (stripping the scheme, any userinfo,
+/// port, and path/query/fragment), for matching against a
+/// `uniformResourceIdentifier` name constraint.
+fn uri_host(uri: &str) -> Option<&str> {
+    let after_scheme = match uri.split_once("://") {
+        Some((_, rest)) => rest,
+        None => uri,
+    };
+    let authority = after_scheme
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or(after_scheme);
+    let host_and_port = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    let host = if host_and_port.starts_with('[') {
+        // IPv6 literal, e.g. `[::1]:8443`.
+        host_and_port.split(']').next().map(|h| &h[1..])?
+    } else {
+        host_and_port.split_once(':').map_or(host_and_port, |(h, _)| h)
+    };
+    if host.is_empty() {
+        None
+    } else {
+        Some(host)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptography_x509::name::{GeneralName, UnvalidatedIA5String};
+
+    use super::{dns_suffix_matches, NameConstraints};
+    use crate::types::DNSName;
+
+    fn dns(name: &str) -> GeneralName<'_> {
+        GeneralName::DNSName(UnvalidatedIA5String(name))
+    }
+
+    #[test]
+    fn test_dns_suffix_matches() {
+        let example_com = DNSName::new("example.com").unwrap();
+
+        assert!(dns_suffix_matches(
+            &example_com,
+            &DNSName::new("example.com").unwrap()
+        ));
+        assert!(dns_suffix_matches(
+            &example_com,
+            &DNSName::new("host.example.com").unwrap()
+        ));
+        assert!(dns_suffix_matches(
+            &example_com,
+            &DNSName::new("a.b.example.com").unwrap()
+        ));
+        // Case-insensitive, per DNSName's PartialEq.
+        assert!(dns_suffix_matches(
+            &example_com,
+            &DNSName::new("HOST.EXAMPLE.com").unwrap()
+        ));
+        // A same-suffix-but-different-label name must not match: this is
+        // exactly what a raw substring test would get wrong.
+        assert!(!dns_suffix_matches(
+            &example_com,
+            &DNSName::new("notexample.com").unwrap()
+        ));
+        assert!(!dns_suffix_matches(
+            &example_com,
+            &DNSName::new("example.com.evil.com").unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_permits_excluded_wins_over_permitted() {
+        let mut nc = NameConstraints::default();
+        nc.dns.permitted = vec![vec![dns("example.com")]];
+        nc.dns.excluded = vec![dns("secret.example.com")];
+
+        assert!(nc.permits(&dns("host.example.com")).is_ok());
+        assert!(nc.permits(&dns("secret.example.com")).is_err());
+        assert!(nc.permits(&dns("sub.secret.example.com")).is_err());
+    }
+
+    #[test]
+    fn test_permits_unconstrained_by_default() {
+        let nc = NameConstraints::default();
+        assert!(nc.permits(&dns("anything.example")).is_ok());
+    }
+
+    #[test]
+    fn test_permits_intersects_across_chain() {
+        // A root CA permits all of `example.com`; an intermediate further
+        // narrows that to `eng.example.com`. A child CA's permittedSubtrees
+        // must narrow, not widen, what its issuer already permitted.
+        let mut nc = NameConstraints::default();
+        nc.dns.permitted = vec![
+            vec![dns("example.com")],
+            vec![dns("eng.example.com")],
+        ];
+
+        assert!(nc.permits(&dns("host.eng.example.com")).is_ok());
+        // Permitted by the root's list, but not by the intermediate's --
+        // the union-only (pre-fix) behavior would have wrongly allowed this.
+        assert!(nc.permits(&dns("host.sales.example.com")).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Validation profiles: the EKU(s), permitted algorithm sets, and extension
+//! policies that distinguish (e.g.) a Web PKI TLS server chain from a TLS
+//! client chain. `Policy` is generic over a [`Profile`] rather than
+//! hardcoding the Web PKI server-auth rules, so callers can validate other
+//! kinds of chains without forking the validator.
+
+use std::collections::HashSet;
+
+use cryptography_x509::common::AlgorithmIdentifier;
+use cryptography_x509::oid::{
+    AUTHORITY_INFORMATION_ACCESS_OID, AUTHORITY_KEY_IDENTIFIER_OID, BASIC_CONSTRAINTS_OID,
+    CERTIFICATE_POLICIES_OID, EKU_CLIENT_AUTH_OID, EKU_EMAIL_PROTECTION_OID, EKU_SERVER_AUTH_OID,
+    EXTENDED_KEY_USAGE_OID, INHIBIT_ANY_POLICY_OID, KEY_USAGE_OID, NAME_CONSTRAINTS_OID,
+    POLICY_CONSTRAINTS_OID, POLICY_MAPPINGS_OID, SUBJECT_ALTERNATIVE_NAME_OID,
+    SUBJECT_DIRECTORY_ATTRIBUTES_OID, SUBJECT_KEY_IDENTIFIER_OID,
+};
+
+use asn1::ObjectIdentifier;
+
+use super::extension::{ca, common, ee, Criticality, ExtensionPolicy};
+use super::{WEBPKI_PERMITTED_SIGNATURE_ALGORITHMS, WEBPKI_PERMITTED_SPKI_ALGORITHMS};
+use crate::ops::CryptoOps;
+
+/// Supplies the parts of chain validation that vary by what kind of
+/// certificate is being validated: the leaf's required extended key
+/// usage(s), the permitted SPKI/signature algorithm sets, and the
+/// common/CA/EE `ExtensionPolicy` lists.
+pub trait Profile<B: CryptoOps> {
+    /// The extended key usage that EE certificates validated under this
+    /// profile must assert, if they assert an EKU extension at all. `None`
+    /// means this profile's certificates aren't expected to use the EKU
+    /// extension (e.g. RPKI resource certificates).
+    fn extended_key_usage(&self) -> Option<ObjectIdentifier>;
+
+    /// The set of permitted `SubjectPublicKeyInfo` algorithms.
+    ///
+    /// Defaults to the CA/B Forum Baseline Requirements' set, since most
+    /// profiles (server-auth, client-auth) are still Web PKI chains.
+    fn permitted_public_key_algorithms(&self) -> HashSet<AlgorithmIdentifier<'static>> {
+        WEBPKI_PERMITTED_SPKI_ALGORITHMS
+            .clone()
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// The set of permitted signature algorithms.
+    ///
+    /// Defaults to the CA/B Forum Baseline Requirements' set, for the same
+    /// reason as [`Self::permitted_public_key_algorithms`].
+    fn permitted_signature_algorithms(&self) -> HashSet<AlgorithmIdentifier<'static>> {
+        WEBPKI_PERMITTED_SIGNATURE_ALGORITHMS
+            .clone()
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Extension policies applied to every certificate in the chain,
+    /// regardless of its position.
+    fn common_extension_policies(&self) -> Vec<ExtensionPolicy<B>>;
+
+    /// Extension policies applied only to CA (non-leaf) certificates.
+    fn ca_extension_policies(&self) -> Vec<ExtensionPolicy<B>>;
+
+    /// Extension policies applied only to EE (leaf) certificates.
+    fn ee_extension_policies(&self) -> Vec<ExtensionPolicy<B>>;
+}
+
+fn webpki_common_extension_policies<B: CryptoOps>() -> Vec<ExtensionPolicy<B>> {
+    Vec::from([
+        // 5280 4.2.1.8: Subject Directory Attributes
+        ExtensionPolicy::maybe_present(
+            SUBJECT_DIRECTORY_ATTRIBUTES_OID,
+            Criticality::NonCritical,
+            None,
+        ),
+        // 5280 4.2.2.1: Authority Information Access
+        ExtensionPolicy::maybe_present(
+            AUTHORITY_INFORMATION_ACCESS_OID,
+            Criticality::NonCritical,
+            Some(common::authority_information_access),
+        ),
+        // 5280 4.2.1.12: Extended Key Usage
+        //
+        // NOTE: CABF requires EKUs in all subscriber certs and in many
+        // non-root CA certs, but validators widely ignore this
+        // requirement and treat a missing EKU as "any EKU".
+        // We choose to be permissive here.
+        ExtensionPolicy::maybe_present(
+            EXTENDED_KEY_USAGE_OID,
+            Criticality::NonCritical,
+            Some(common::extended_key_usage),
+        ),
+        // 5280 4.2.1.4: Certificate Policies
+        //
+        // Processed by the valid-policy tree in `valid_issuer`/`permits_ee`
+        // rather than by a per-certificate validator.
+        ExtensionPolicy::maybe_present(CERTIFICATE_POLICIES_OID, Criticality::Agnostic, None),
+    ])
+}
+
+fn webpki_ca_extension_policies<B: CryptoOps>() -> Vec<ExtensionPolicy<B>> {
+    Vec::from([
+        // 5280 4.2.1.1: Authority Key Identifier
+        ExtensionPolicy::maybe_present(
+            AUTHORITY_KEY_IDENTIFIER_OID,
+            Criticality::NonCritical,
+            Some(ca::authority_key_identifier),
+        ),
+        // 5280 4.2.1.2: Subject Key Identifier
+        // NOTE: CABF requires SKI in CA certificates, but many older CAs lack it.
+        // We choose to be permissive here.
+        ExtensionPolicy::maybe_present(
+            SUBJECT_KEY_IDENTIFIER_OID,
+            Criticality::NonCritical,
+            None,
+        ),
+        // 5280 4.2.1.3: Key Usage
+        ExtensionPolicy::present(KEY_USAGE_OID, Criticality::Agnostic, Some(ca::key_usage)),
+        // 5280 4.2.1.9: Basic Constraints
+        ExtensionPolicy::present(
+            BASIC_CONSTRAINTS_OID,
+            Criticality::Critical,
+            Some(ca::basic_constraints),
+        ),
+        // 5280 4.2.1.10: Name Constraints
+        // NOTE: MUST be critical in 5280, but CABF relaxes to MAY.
+        ExtensionPolicy::maybe_present(
+            NAME_CONSTRAINTS_OID,
+            Criticality::Agnostic,
+            Some(ca::name_constraints),
+        ),
+        // 5280 4.2.1.11: Policy Constraints
+        ExtensionPolicy::maybe_present(POLICY_CONSTRAINTS_OID, Criticality::Critical, None),
+        // 5280 4.2.1.5: Policy Mappings
+        ExtensionPolicy::maybe_present(POLICY_MAPPINGS_OID, Criticality::NonCritical, None),
+        // 5280 4.2.1.14: Inhibit anyPolicy
+        ExtensionPolicy::maybe_present(INHIBIT_ANY_POLICY_OID, Criticality::Critical, None),
+    ])
+}
+
+/// The Web PKI TLS server-authentication profile: the original, hardcoded
+/// behavior of `Policy`, and still the default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WebPkiServerAuth;
+
+impl<B: CryptoOps> Profile<B> for WebPkiServerAuth {
+    fn extended_key_usage(&self) -> Option<ObjectIdentifier> {
+        Some(EKU_SERVER_AUTH_OID.clone())
+    }
+
+    fn common_extension_policies(&self) -> Vec<ExtensionPolicy<B>> {
+        webpki_common_extension_policies()
+    }
+
+    fn ca_extension_policies(&self) -> Vec<ExtensionPolicy<B>> {
+        webpki_ca_extension_policies()
+    }
+
+    fn ee_extension_policies(&self) -> Vec<ExtensionPolicy<B>> {
+        Vec::from([
+            // 5280 4.2.1.1.: Authority Key Identifier
+            ExtensionPolicy::present(AUTHORITY_KEY_IDENTIFIER_OID, Criticality::NonCritical, None),
+            // 5280 4.2.1.3: Key Usage
+            ExtensionPolicy::maybe_present(KEY_USAGE_OID, Criticality::Agnostic, None),
+            // CA/B 7.1.2.7.12 Subscriber Certificate Subject Alternative Name
+            ExtensionPolicy::present(
+                SUBJECT_ALTERNATIVE_NAME_OID,
+                Criticality::Agnostic,
+                Some(ee::subject_alternative_name),
+            ),
+            // 5280 4.2.1.9: Basic Constraints
+            ExtensionPolicy::maybe_present(
+                BASIC_CONSTRAINTS_OID,
+                Criticality::Agnostic,
+                Some(ee::basic_constraints),
+            ),
+            // 5280 4.2.1.10: Name Constraints
+            ExtensionPolicy::not_present(NAME_CONSTRAINTS_OID),
+        ])
+    }
+}
+
+/// The Web PKI TLS client-authentication profile: same CA/B algorithm and
+/// CA-position rules as [`WebPkiServerAuth`], but EKU `clientAuth` and
+/// without the server-auth profile's requirement that EEs assert a
+/// `subjectAltName` (client certificates commonly identify a user by
+/// `subject`, not by SAN).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WebPkiClientAuth;
+
+impl<B: CryptoOps> Profile<B> for WebPkiClientAuth {
+    fn extended_key_usage(&self) -> Option<ObjectIdentifier> {
+        Some(EKU_CLIENT_AUTH_OID.clone())
+    }
+
+    fn common_extension_policies(&self) -> Vec<ExtensionPolicy<B>> {
+        webpki_common_extension_policies()
+    }
+
+    fn ca_extension_policies(&self) -> Vec<ExtensionPolicy<B>> {
+        webpki_ca_extension_policies()
+    }
+
+    fn ee_extension_policies(&self) -> Vec<ExtensionPolicy<B>> {
+        Vec::from([
+            // 5280 4.2.1.1.: Authority Key Identifier
+            ExtensionPolicy::present(AUTHORITY_KEY_IDENTIFIER_OID, Criticality::NonCritical, None),
+            // 5280 4.2.1.3: Key Usage
+            ExtensionPolicy::maybe_present(KEY_USAGE_OID, Criticality::Agnostic, None),
+            // Unlike server-auth, a subjectAltName isn't required here.
+            ExtensionPolicy::maybe_present(
+                SUBJECT_ALTERNATIVE_NAME_OID,
+                Criticality::Agnostic,
+                Some(ee::subject_alternative_name),
+            ),
+            // 5280 4.2.1.9: Basic Constraints
+            ExtensionPolicy::maybe_present(
+                BASIC_CONSTRAINTS_OID,
+                Criticality::Agnostic,
+                Some(ee::basic_constraints),
+            ),
+            // 5280 4.2.1.10: Name Constraints
+            ExtensionPolicy::not_present(NAME_CONSTRAINTS_OID),
+        ])
+    }
+}
+
+/// The S/MIME signer profile (RFC 8550): same CA/B algorithm and CA-position
+/// rules as [`WebPkiServerAuth`]/[`WebPkiClientAuth`], but EKU
+/// `emailProtection` and without the server-auth profile's requirement that
+/// EEs assert a `subjectAltName` (a signer's email address is commonly
+/// carried in `subject`, not necessarily in an rfc822Name SAN).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Smime;
+
+impl<B: CryptoOps> Profile<B> for Smime {
+    fn extended_key_usage(&self) -> Option<ObjectIdentifier> {
+        Some(EKU_EMAIL_PROTECTION_OID.clone())
+    }
+
+    fn common_extension_policies(&self) -> Vec<ExtensionPolicy<B>> {
+        webpki_common_extension_policies()
+    }
+
+    fn ca_extension_policies(&self) -> Vec<ExtensionPolicy<B>> {
+        webpki_ca_extension_policies()
+    }
+
+    fn ee_extension_policies(&self) -> Vec<ExtensionPolicy<B>> {
+        Vec::from([
+            // 5280 4.2.1.1.: Authority Key Identifier
+            ExtensionPolicy::present(AUTHORITY_KEY_IDENTIFIER_OID, Criticality::NonCritical, None),
+            // 5280 4.2.1.3: Key Usage
+            ExtensionPolicy::maybe_present(KEY_USAGE_OID, Criticality::Agnostic, None),
+            // Unlike server-auth, a subjectAltName isn't required here.
+            ExtensionPolicy::maybe_present(
+                SUBJECT_ALTERNATIVE_NAME_OID,
+                Criticality::Agnostic,
+                Some(ee::subject_alternative_name),
+            ),
+            // 5280 4.2.1.9: Basic Constraints
+            ExtensionPolicy::maybe_present(
+                BASIC_CONSTRAINTS_OID,
+                Criticality::Agnostic,
+                Some(ee::basic_constraints),
+            ),
+            // 5280 4.2.1.10: Name Constraints
+            ExtensionPolicy::not_present(NAME_CONSTRAINTS_OID),
+        ])
+    }
+}
@@ -0,0 +1,400 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! RFC 3779 IP address and AS number resource sets.
+//!
+//! These back the `sbgp-ipAddrBlock` and `sbgp-autonomousSysNum` extensions
+//! used by the RPKI resource-certificate profile (`policy::rpki`) to decide
+//! whether a certificate's claimed resources are "encompassed" by its
+//! issuer's.
+
+use asn1::BitString;
+
+use crate::ValidationError;
+
+#[derive(asn1::Asn1Read, Clone)]
+struct IpAddressRange<'a> {
+    min: BitString<'a>,
+    max: BitString<'a>,
+}
+
+#[derive(asn1::Asn1Read, Clone)]
+enum IpAddressOrRange<'a> {
+    AddressPrefix(BitString<'a>),
+    AddressRange(IpAddressRange<'a>),
+}
+
+#[derive(asn1::Asn1Read, Clone)]
+enum IpAddressChoice<'a> {
+    Inherit(asn1::Null),
+    AddressesOrRanges(asn1::SequenceOf<'a, IpAddressOrRange<'a>>),
+}
+
+#[derive(asn1::Asn1Read, Clone)]
+struct IpAddressFamily<'a> {
+    addr_family: &'a [u8],
+    ip_address_choice: IpAddressChoice<'a>,
+}
+
+type IpAddrBlocks<'a> = asn1::SequenceOf<'a, IpAddressFamily<'a>>;
+
+#[derive(asn1::Asn1Read, Clone, Copy)]
+struct AsIdRange {
+    min: u32,
+    max: u32,
+}
+
+#[derive(asn1::Asn1Read, Clone, Copy)]
+enum AsIdOrRange {
+    Id(u32),
+    Range(AsIdRange),
+}
+
+#[derive(asn1::Asn1Read, Clone)]
+enum AsIdentifierChoice<'a> {
+    Inherit(asn1::Null),
+    AsIdsOrRanges(asn1::SequenceOf<'a, AsIdOrRange>),
+}
+
+#[derive(asn1::Asn1Read, Clone)]
+struct AsIdentifiers<'a> {
+    #[explicit(0)]
+    asnum: Option<AsIdentifierChoice<'a>>,
+    // Routing Domain Identifiers (RFC 3779 §3.2.3.2) are legacy and unused
+    // by modern RPKI; we parse past them but don't otherwise act on them.
+    #[explicit(1)]
+    #[allow(dead_code)]
+    rdi: Option<AsIdentifierChoice<'a>>,
+}
+
+/// An inclusive address range, normalized from either the `addressPrefix`
+/// or `addressRange` alternative of `IPAddressOrRange`. IPv4 and IPv6
+/// values are both stored as a `u128`, occupying the low-order 32 or 128
+/// bits respectively; `is_v6` disambiguates them so a v4 block is never
+/// compared against a v6 one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct IpBlock {
+    is_v6: bool,
+    min: u128,
+    max: u128,
+}
+
+impl IpBlock {
+    fn contains(&self, other: &IpBlock) -> bool {
+        self.is_v6 == other.is_v6 && other.min >= self.min && other.max <= self.max
+    }
+}
+
+fn addr_value(is_v6: bool, bytes: &[u8]) -> u128 {
+    let width = if is_v6 { 16 } else { 4 };
+    let mut buf = [0u8; 16];
+    let n = bytes.len().min(width);
+    buf[16 - width..16 - width + n].copy_from_slice(&bytes[..n]);
+    u128::from_be_bytes(buf)
+}
+
+/// Expands a bit string `addressPrefix` into its inclusive `(min, max)`
+/// range: the bits present fix the high-order bits of the address, and
+/// every bit after the prefix ranges over both 0 and 1.
+fn prefix_to_range(is_v6: bool, bs: &BitString<'_>) -> (u128, u128) {
+    let width_bits = if is_v6 { 128 } else { 32 };
+    let total_bits = u32::try_from(bs.as_bytes().len() * 8).unwrap_or(width_bits);
+    let prefix_len = total_bits - u32::from(bs.padding_bits());
+    let min = addr_value(is_v6, bs.as_bytes());
+    let host_bits = width_bits.saturating_sub(prefix_len.min(width_bits));
+    let mask: u128 = if host_bits >= 128 {
+        u128::MAX
+    } else {
+        (1u128 << host_bits) - 1
+    };
+    (min, min | mask)
+}
+
+fn ip_block_from_choice(
+    is_v6: bool,
+    aor: &IpAddressOrRange<'_>,
+) -> Result<IpBlock, ValidationError> {
+    let (min, max) = match aor {
+        IpAddressOrRange::AddressPrefix(bs) => prefix_to_range(is_v6, bs),
+        IpAddressOrRange::AddressRange(range) => (
+            addr_value(is_v6, range.min.as_bytes()),
+            addr_value(is_v6, range.max.as_bytes()),
+        ),
+    };
+    if min > max {
+        return Err(ValidationError::Other(
+            "invalid IP address range in sbgp-ipAddrBlock".to_string(),
+        ));
+    }
+    Ok(IpBlock { is_v6, min, max })
+}
+
+/// A canonicalized, sorted set of `IpBlock`s.
+#[derive(Clone, Default, Debug)]
+struct IpBlockSet(Vec<IpBlock>);
+
+impl IpBlockSet {
+    fn from_blocks(mut blocks: Vec<IpBlock>) -> Self {
+        blocks.sort_by_key(|b| (b.is_v6, b.min, b.max));
+        Self(blocks)
+    }
+
+    /// Returns true if every block in `self` fits entirely within some
+    /// single block of `parent`.
+    ///
+    /// NOTE: this doesn't merge adjacent or overlapping `parent` blocks
+    /// before checking containment, so (in principle) a child block that
+    /// only fits when two separate but contiguous parent blocks are
+    /// combined would be conservatively rejected here. Conformant RPKI
+    /// resource certificates are expected to list maximally-merged blocks
+    /// already (RFC 3779 §2.2.3.6/3.2.3.5), so this is not expected to
+    /// matter in practice.
+    fn is_subset_of(&self, parent: &IpBlockSet) -> bool {
+        self.0
+            .iter()
+            .all(|child| parent.0.iter().any(|p| p.contains(child)))
+    }
+}
+
+/// A single AS number range, from `ASIdOrRange`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct AsBlock {
+    min: u32,
+    max: u32,
+}
+
+impl AsBlock {
+    fn contains(&self, other: &AsBlock) -> bool {
+        other.min >= self.min && other.max <= self.max
+    }
+}
+
+/// A canonicalized, sorted set of `AsBlock`s.
+#[derive(Clone, Default, Debug)]
+struct AsBlockSet(Vec<AsBlock>);
+
+impl AsBlockSet {
+    fn from_blocks(mut blocks: Vec<AsBlock>) -> Self {
+        blocks.sort_by_key(|b| (b.min, b.max));
+        Self(blocks)
+    }
+
+    fn is_subset_of(&self, parent: &AsBlockSet) -> bool {
+        self.0
+            .iter()
+            .all(|child| parent.0.iter().any(|p| p.contains(child)))
+    }
+}
+
+/// A resource set that may explicitly inherit its issuer's resources
+/// (RFC 3779 §2.2.3.3/3.2.3.3, the `inherit` alternative) instead of
+/// asserting its own.
+#[derive(Clone, Debug)]
+enum Inheritable<T> {
+    Inherit,
+    Resources(T),
+}
+
+/// The RFC 3779 resources asserted by a single certificate, split out by
+/// family. `None` for a given family means the certificate claims no
+/// resources of that kind at all, which is distinct from (and stricter
+/// than) an explicit empty list.
+#[derive(Clone, Default, Debug)]
+struct CertificateResources {
+    ipv4: Option<Inheritable<IpBlockSet>>,
+    ipv6: Option<Inheritable<IpBlockSet>>,
+    asn: Option<Inheritable<AsBlockSet>>,
+}
+
+type IpFamilyClaims = (Option<Inheritable<IpBlockSet>>, Option<Inheritable<IpBlockSet>>);
+
+/// Parses a `sbgp-ipAddrBlock` extension value into per-family resource
+/// claims. Address families other than IPv4 and IPv6 (identified by their
+/// 2-byte AFI prefix, RFC 3779 §2.2.3.2) are ignored.
+fn parse_ip_resources(der: &[u8]) -> Result<IpFamilyClaims, ValidationError> {
+    let families: IpAddrBlocks<'_> = asn1::parse_single(der).map_err(|_| {
+        ValidationError::Other("invalid sbgp-ipAddrBlock extension".to_string())
+    })?;
+
+    let mut ipv4 = None;
+    let mut ipv6 = None;
+    for family in families.clone() {
+        let is_v6 = match family.addr_family {
+            [0, 1] => false,
+            [0, 2] => true,
+            _ => continue,
+        };
+
+        let resources = match family.ip_address_choice {
+            IpAddressChoice::Inherit(_) => Inheritable::Inherit,
+            IpAddressChoice::AddressesOrRanges(seq) => {
+                let mut blocks = Vec::new();
+                for aor in seq {
+                    blocks.push(ip_block_from_choice(is_v6, &aor)?);
+                }
+                Inheritable::Resources(IpBlockSet::from_blocks(blocks))
+            }
+        };
+
+        if is_v6 {
+            ipv6 = Some(resources);
+        } else {
+            ipv4 = Some(resources);
+        }
+    }
+
+    Ok((ipv4, ipv6))
+}
+
+/// Parses a `sbgp-autonomousSysNum` extension value into an AS number
+/// resource claim.
+fn parse_as_resources(der: &[u8]) -> Result<Option<Inheritable<AsBlockSet>>, ValidationError> {
+    let ids: AsIdentifiers<'_> = asn1::parse_single(der).map_err(|_| {
+        ValidationError::Other("invalid sbgp-autonomousSysNum extension".to_string())
+    })?;
+
+    let asnum = match ids.asnum {
+        None => return Ok(None),
+        Some(AsIdentifierChoice::Inherit(_)) => Inheritable::Inherit,
+        Some(AsIdentifierChoice::AsIdsOrRanges(seq)) => {
+            let mut blocks = Vec::new();
+            for aor in seq {
+                blocks.push(match aor {
+                    AsIdOrRange::Id(id) => AsBlock { min: id, max: id },
+                    AsIdOrRange::Range(r) => {
+                        if r.min > r.max {
+                            return Err(ValidationError::Other(
+                                "invalid AS number range in sbgp-autonomousSysNum".to_string(),
+                            ));
+                        }
+                        AsBlock {
+                            min: r.min,
+                            max: r.max,
+                        }
+                    }
+                });
+            }
+            Inheritable::Resources(AsBlockSet::from_blocks(blocks))
+        }
+    };
+
+    Ok(Some(asnum))
+}
+
+/// Parses the `sbgp-ipAddrBlock` and/or `sbgp-autonomousSysNum` extension
+/// values (either may be absent) into a single `CertificateResources`.
+pub(crate) fn parse_resources(
+    ip_addr_block: Option<&[u8]>,
+    autonomous_sys_num: Option<&[u8]>,
+) -> Result<CertificateResources, ValidationError> {
+    let (ipv4, ipv6) = match ip_addr_block {
+        Some(der) => parse_ip_resources(der)?,
+        None => (None, None),
+    };
+    let asn = match autonomous_sys_num {
+        Some(der) => parse_as_resources(der)?,
+        None => None,
+    };
+
+    Ok(CertificateResources { ipv4, ipv6, asn })
+}
+
+/// The effective (fully `inherit`-resolved) resources in force at some
+/// point in a chain: the trust anchor's own claims, or a descendant's
+/// claims after being checked against its issuer's.
+#[derive(Clone, Default, Debug)]
+pub(crate) struct EffectiveResources {
+    ipv4: Option<IpBlockSet>,
+    ipv6: Option<IpBlockSet>,
+    asn: Option<AsBlockSet>,
+}
+
+fn resolve_ip<'a>(
+    claim: &'a Option<Inheritable<IpBlockSet>>,
+    parent: &'a Option<IpBlockSet>,
+) -> Result<Option<IpBlockSet>, ValidationError> {
+    match claim {
+        None => Ok(None),
+        Some(Inheritable::Inherit) => Ok(parent.clone()),
+        Some(Inheritable::Resources(set)) => match parent {
+            Some(parent_set) if set.is_subset_of(parent_set) => Ok(Some(set.clone())),
+            Some(_) => Err(ValidationError::Other(
+                "certificate's IP resources are not encompassed by its issuer's".to_string(),
+            )),
+            None => Err(ValidationError::Other(
+                "certificate claims IP resources its issuer doesn't have".to_string(),
+            )),
+        },
+    }
+}
+
+fn resolve_as(
+    claim: &Option<Inheritable<AsBlockSet>>,
+    parent: &Option<AsBlockSet>,
+) -> Result<Option<AsBlockSet>, ValidationError> {
+    match claim {
+        None => Ok(None),
+        Some(Inheritable::Inherit) => Ok(parent.clone()),
+        Some(Inheritable::Resources(set)) => match parent {
+            Some(parent_set) if set.is_subset_of(parent_set) => Ok(Some(set.clone())),
+            Some(_) => Err(ValidationError::Other(
+                "certificate's AS resources are not encompassed by its issuer's".to_string(),
+            )),
+            None => Err(ValidationError::Other(
+                "certificate claims AS resources its issuer doesn't have".to_string(),
+            )),
+        },
+    }
+}
+
+impl EffectiveResources {
+    /// Builds the trust anchor's effective resources directly from its own
+    /// extensions: a trust anchor can't `inherit` (there's nothing above
+    /// it to inherit from).
+    pub(crate) fn from_trust_anchor(
+        ip_addr_block: Option<&[u8]>,
+        autonomous_sys_num: Option<&[u8]>,
+    ) -> Result<Self, ValidationError> {
+        let claimed = parse_resources(ip_addr_block, autonomous_sys_num)?;
+        let resolve_root = |claim: Option<Inheritable<IpBlockSet>>| match claim {
+            None => Ok(None),
+            Some(Inheritable::Inherit) => Err(ValidationError::Other(
+                "trust anchor cannot inherit IP resources".to_string(),
+            )),
+            Some(Inheritable::Resources(set)) => Ok(Some(set)),
+        };
+        let asn = match claimed.asn {
+            None => None,
+            Some(Inheritable::Inherit) => {
+                return Err(ValidationError::Other(
+                    "trust anchor cannot inherit AS resources".to_string(),
+                ))
+            }
+            Some(Inheritable::Resources(set)) => Some(set),
+        };
+
+        Ok(Self {
+            ipv4: resolve_root(claimed.ipv4)?,
+            ipv6: resolve_root(claimed.ipv6)?,
+            asn,
+        })
+    }
+
+    /// Checks `ip_addr_block`/`autonomous_sys_num` (a descendant certificate's
+    /// raw extension values) against `self` (the issuer's effective
+    /// resources) and returns the descendant's own effective resources on
+    /// success.
+    pub(crate) fn encompass(
+        &self,
+        ip_addr_block: Option<&[u8]>,
+        autonomous_sys_num: Option<&[u8]>,
+    ) -> Result<Self, ValidationError> {
+        let claimed = parse_resources(ip_addr_block, autonomous_sys_num)?;
+        Ok(Self {
+            ipv4: resolve_ip(&claimed.ipv4, &self.ipv4)?,
+            ipv6: resolve_ip(&claimed.ipv6, &self.ipv6)?,
+            asn: resolve_as(&claimed.asn, &self.asn)?,
+        })
+    }
+}
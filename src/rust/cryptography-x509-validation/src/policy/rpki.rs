@@ -0,0 +1,69 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! The RPKI resource-certificate profile (RFC 6487), built on top of the
+//! RFC 3779 resource sets in [`super::resources`].
+//!
+//! Unlike the Web PKI profiles, RPKI resource certificates aren't
+//! validated against a subject name or an extended key usage -- they
+//! exist to attest to a holder's right to use a set of IP address
+//! and/or AS number resources, carried in the `sbgp-ipAddrBlock` and
+//! `sbgp-autonomousSysNum` extensions. This profile's extension-policy
+//! lists only account for those two extensions (so they don't trip the
+//! "unaccounted-for critical extension" check); the actual "child
+//! resources must be encompassed by the issuer's" enforcement happens
+//! separately, via [`super::Policy::valid_issuer`] and
+//! [`super::Policy::permits_ee`], the same way name constraints and
+//! certificate policies are threaded through the chain.
+
+use asn1::ObjectIdentifier;
+
+use super::extension::{Criticality, ExtensionPolicy};
+use super::Profile;
+use crate::ops::CryptoOps;
+
+/// `id-pe-ipAddrBlock`, RFC 3779 §2.2.3.1.
+pub(crate) const SBGP_IP_ADDR_BLOCK_OID: ObjectIdentifier = asn1::oid!(1, 3, 6, 1, 5, 5, 7, 1, 7);
+
+/// `id-pe-autonomousSysNum`, RFC 3779 §3.2.3.1.
+pub(crate) const SBGP_AUTONOMOUS_SYS_NUM_OID: ObjectIdentifier =
+    asn1::oid!(1, 3, 6, 1, 5, 5, 7, 1, 8);
+
+/// The RPKI resource-certificate profile, per RFC 6487.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RpkiCertificate;
+
+impl<B: CryptoOps> Profile<B> for RpkiCertificate {
+    fn extended_key_usage(&self) -> Option<ObjectIdentifier> {
+        // RFC 6487 §4.8.5: resource certificates don't use the EKU
+        // extension at all (the one exception, router certificates using
+        // id-kp-bgpsec-router, is out of scope here).
+        None
+    }
+
+    fn common_extension_policies(&self) -> Vec<ExtensionPolicy<B>> {
+        Vec::from([
+            // RFC 3779 §2.3: accounted for here so that its criticality is
+            // checked and it doesn't trip the unaccounted-for-critical-
+            // extension check; the actual resource checks happen via the
+            // accumulated `EffectiveResources` state in `valid_issuer`/
+            // `permits_ee`.
+            ExtensionPolicy::maybe_present(SBGP_IP_ADDR_BLOCK_OID, Criticality::Critical, None),
+            // RFC 3779 §3.3: same as above, for AS numbers.
+            ExtensionPolicy::maybe_present(
+                SBGP_AUTONOMOUS_SYS_NUM_OID,
+                Criticality::Critical,
+                None,
+            ),
+        ])
+    }
+
+    fn ca_extension_policies(&self) -> Vec<ExtensionPolicy<B>> {
+        Vec::new()
+    }
+
+    fn ee_extension_policies(&self) -> Vec<ExtensionPolicy<B>> {
+        Vec::new()
+    }
+}
@@ -0,0 +1,10 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Name types used by [`crate::policy`] to match a certificate's asserted
+//! names (SANs, name constraints subtrees) against a validation `Subject`.
+
+pub use cryptography_x509::common::{
+    DNSName, DNSPattern, IPAddress, IPConstraint, RFC822Constraint, RFC822Name,
+};
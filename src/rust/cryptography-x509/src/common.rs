@@ -31,6 +31,10 @@ pub enum AlgorithmParameters<'a> {
     Sha384(Option<asn1::Null>),
     #[defined_by(oid::SHA512_OID)]
     Sha512(Option<asn1::Null>),
+    #[defined_by(oid::SHA512_224_OID)]
+    Sha512_224(Option<asn1::Null>),
+    #[defined_by(oid::SHA512_256_OID)]
+    Sha512_256(Option<asn1::Null>),
     #[defined_by(oid::SHA3_224_OID)]
     Sha3_224(Option<asn1::Null>),
     #[defined_by(oid::SHA3_256_OID)]
@@ -49,6 +53,8 @@ pub enum AlgorithmParameters<'a> {
     // but Java 11 (up to at least 11.0.19) encodes them
     // with NULL parameters. The JDK team is looking to
     // backport the fix as of June 2023.
+    #[defined_by(oid::ECDSA_WITH_SHA1_OID)]
+    EcDsaWithSha1(Option<asn1::Null>),
     #[defined_by(oid::ECDSA_WITH_SHA224_OID)]
     EcDsaWithSha224(Option<asn1::Null>),
     #[defined_by(oid::ECDSA_WITH_SHA256_OID)]
@@ -57,6 +63,10 @@ pub enum AlgorithmParameters<'a> {
     EcDsaWithSha384(Option<asn1::Null>),
     #[defined_by(oid::ECDSA_WITH_SHA512_OID)]
     EcDsaWithSha512(Option<asn1::Null>),
+    #[defined_by(oid::ECDSA_WITH_SHA512_224_OID)]
+    EcDsaWithSha512_224(Option<asn1::Null>),
+    #[defined_by(oid::ECDSA_WITH_SHA512_256_OID)]
+    EcDsaWithSha512_256(Option<asn1::Null>),
 
     #[defined_by(oid::ECDSA_WITH_SHA3_224_OID)]
     EcDsaWithSha3_224,
@@ -80,6 +90,10 @@ pub enum AlgorithmParameters<'a> {
     RsaWithSha384(Option<asn1::Null>),
     #[defined_by(oid::RSA_WITH_SHA512_OID)]
     RsaWithSha512(Option<asn1::Null>),
+    #[defined_by(oid::RSA_WITH_SHA512_224_OID)]
+    RsaWithSha512_224(Option<asn1::Null>),
+    #[defined_by(oid::RSA_WITH_SHA512_256_OID)]
+    RsaWithSha512_256(Option<asn1::Null>),
 
     #[defined_by(oid::RSA_WITH_SHA3_224_OID)]
     RsaWithSha3_224(Option<asn1::Null>),
@@ -96,6 +110,19 @@ pub enum AlgorithmParameters<'a> {
     #[defined_by(oid::RSASSA_PSS_OID)]
     RsaPss(Option<Box<RsaPssParameters<'a>>>),
 
+    // RFC 5084: used as the content encryption algorithm of a CMS
+    // `AuthEnvelopedData`'s `EncryptedContentInfo` in place of one of the
+    // `AesNNNCbc` variants, when the content is protected with AES-GCM
+    // instead of CBC.
+    #[defined_by(oid::AES_128_GCM_OID)]
+    Aes128Gcm(GCMParameters<'a>),
+    #[defined_by(oid::AES_192_GCM_OID)]
+    Aes192Gcm(GCMParameters<'a>),
+    #[defined_by(oid::AES_256_GCM_OID)]
+    Aes256Gcm(GCMParameters<'a>),
+
+    #[defined_by(oid::DSA_WITH_SHA1_OID)]
+    DsaWithSha1,
     #[defined_by(oid::DSA_WITH_SHA224_OID)]
     DsaWithSha224,
     #[defined_by(oid::DSA_WITH_SHA256_OID)]
@@ -234,6 +261,17 @@ pub const PSS_SHA1_HASH_ALG: AlgorithmIdentifier<'_> = AlgorithmIdentifier {
     params: AlgorithmParameters::Sha1(Some(())),
 };
 
+// From RFC 5084 section 3.2:
+// GCMParameters ::= SEQUENCE {
+//     aes-nonce        OCTET STRING (SIZE(12)),
+//     aes-ICVlen       AES-GCM-ICVlen DEFAULT 12 }
+#[derive(asn1::Asn1Read, asn1::Asn1Write, Hash, Clone, PartialEq, Eq, Debug)]
+pub struct GCMParameters<'a> {
+    pub nonce: &'a [u8],
+    #[default(12u16)]
+    pub icv_len: u16,
+}
+
 // This is defined as an AlgorithmIdentifier in RFC 4055,
 // but the mask generation algorithm **must** contain an AlgorithmIdentifier
 // in its params, so we define it this way.
@@ -273,6 +311,63 @@ pub struct RsaPssParameters<'a> {
     pub _trailer_field: u8,
 }
 
+impl<'a> RsaPssParameters<'a> {
+    /// Validates this `RSASSA-PSS-params` and resolves it to a canonical
+    /// signature configuration: the raw ASN.1 structure (RFC 4055 §3.1)
+    /// permits field combinations that must be rejected before
+    /// verification rather than re-derived and re-checked at every call
+    /// site. Returns the agreed-upon hash algorithm and the salt length
+    /// if, and only if:
+    ///
+    /// * `mask_gen_algorithm.oid` is `id-mgf1`;
+    /// * the MGF1 inner hash (`mask_gen_algorithm.params`) is the same
+    ///   algorithm as `hash_algorithm` -- RFC 4055 only defines
+    ///   combinations where the two agree, and accepting a mismatched
+    ///   pair would silently use a weaker hash than the one a caller
+    ///   thinks it's verifying against;
+    /// * `_trailer_field` is `1`, the only value RFC 4055 defines; and
+    /// * `salt_length` equals the chosen hash's output length, the only
+    ///   convention CA/B-compliant issuers are expected to produce.
+    pub fn as_verification_config(&self) -> Option<(AlgorithmParameters<'a>, u16)> {
+        if self.mask_gen_algorithm.oid != oid::MGF1_OID {
+            return None;
+        }
+
+        // RFC 4055 §2.1 says NULL and absent parameters are equivalent, so
+        // the MGF1 inner hash is compared by OID rather than full
+        // `AlgorithmIdentifier` equality, which would also compare the
+        // NULL-vs-absent encoding of the parameters.
+        if self.mask_gen_algorithm.params.oid() != self.hash_algorithm.oid() {
+            return None;
+        }
+
+        if self._trailer_field != 1 {
+            return None;
+        }
+
+        let digest_length: u16 = match &self.hash_algorithm.params {
+            AlgorithmParameters::Sha1(_) => 20,
+            AlgorithmParameters::Sha224(_) => 28,
+            AlgorithmParameters::Sha256(_) => 32,
+            AlgorithmParameters::Sha384(_) => 48,
+            AlgorithmParameters::Sha512(_) => 64,
+            AlgorithmParameters::Sha512_224(_) => 28,
+            AlgorithmParameters::Sha512_256(_) => 32,
+            AlgorithmParameters::Sha3_224(_) => 28,
+            AlgorithmParameters::Sha3_256(_) => 32,
+            AlgorithmParameters::Sha3_384(_) => 48,
+            AlgorithmParameters::Sha3_512(_) => 64,
+            _ => return None,
+        };
+
+        if self.salt_length != digest_length {
+            return None;
+        }
+
+        Some((self.hash_algorithm.params.clone(), self.salt_length))
+    }
+}
+
 /// A VisibleString ASN.1 element whose contents is not validated as meeting the
 /// requirements (visible characters of IA5), and instead is only known to be
 /// valid UTF-8.
@@ -365,6 +460,50 @@ impl<'a> DNSName<'a> {
             None => None,
         }
     }
+
+    /// The number of `.`-separated labels in this name.
+    ///
+    /// ```rust
+    /// # use cryptography_x509::common::DNSName;
+    /// assert_eq!(DNSName::new("foo.example.com").unwrap().labels(), 3);
+    /// ```
+    pub fn labels(&self) -> usize {
+        self.as_str().split('.').count()
+    }
+
+    /// Returns the trailing `n` labels of this name, or `None` if `n` is 0 or
+    /// this name doesn't have at least `n` labels. `n == self.labels()` returns the
+    /// entire name.
+    ///
+    /// ```rust
+    /// # use cryptography_x509::common::DNSName;
+    /// let name = DNSName::new("foo.example.com").unwrap();
+    /// assert_eq!(name.trailing_n_labels(2), Some("example.com"));
+    /// assert_eq!(name.trailing_n_labels(3), Some("foo.example.com"));
+    /// assert_eq!(name.trailing_n_labels(4), None);
+    /// ```
+    pub fn trailing_n_labels(&self, n: usize) -> Option<&'a str> {
+        let s = self.as_str();
+        if n == 0 || n > self.labels() {
+            return None;
+        }
+
+        // `n`-th `.` from the right, scanning backwards; the suffix starts
+        // just past it. `.` is a single ASCII byte, so the byte index found
+        // here is always a valid `str` boundary.
+        let mut dots_seen = 0;
+        for (i, c) in s.char_indices().rev() {
+            if c == '.' {
+                dots_seen += 1;
+                if dots_seen == n {
+                    return Some(&s[i + 1..]);
+                }
+            }
+        }
+
+        // Fewer than `n` dots means `n` covers the entire name.
+        Some(s)
+    }
 }
 
 impl PartialEq for DNSName<'_> {
@@ -400,18 +539,219 @@ impl<'a> DNSPattern<'a> {
     pub fn matches(&self, name: &DNSName) -> bool {
         match self {
             Self::Exact(pat) => pat == name,
-            Self::Wildcard(pat) => match name.parent() {
-                Some(ref parent) => pat == parent,
-                // No parent means we have a single label; wildcards cannot match single labels.
-                None => false,
-            },
+            Self::Wildcard(pat) => {
+                // The wildcard occupies exactly the entire leftmost label
+                // (enforced by `DNSPattern::new`/`DNSName::new`, which
+                // reject embedded or partial-label wildcards like `f*o` or
+                // non-left-most ones like `foo.*.bar`), so a match requires
+                // `name` to have exactly one more label than `pat`'s fixed
+                // suffix, with that suffix matching the trailing labels of
+                // `name` one-for-one.
+                let suffix_labels = pat.labels();
+                if name.labels() != suffix_labels + 1 {
+                    return false;
+                }
+
+                match (
+                    name.trailing_n_labels(suffix_labels),
+                    pat.trailing_n_labels(suffix_labels),
+                ) {
+                    (Some(name_suffix), Some(pat_suffix)) => {
+                        name_suffix.eq_ignore_ascii_case(pat_suffix)
+                    }
+                    _ => false,
+                }
+            }
+        }
+    }
+}
+
+/// An IPv4 or IPv6 address, as asserted in a certificate's `iPAddress`
+/// `GeneralName` (which encodes the address's raw octets with no framing).
+///
+/// Like [`DNSName`], `PartialEq` compares normalized octets rather than any
+/// particular textual spelling.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IPAddress {
+    V4(std::net::Ipv4Addr),
+    V6(std::net::Ipv6Addr),
+}
+
+impl IPAddress {
+    /// Parses an `IPAddress` from its textual (dotted-quad or colon-hex)
+    /// representation, as used for a validation `Subject`.
+    pub fn from_str(s: &str) -> Option<Self> {
+        if let Ok(addr) = s.parse::<std::net::Ipv4Addr>() {
+            Some(Self::V4(addr))
+        } else if let Ok(addr) = s.parse::<std::net::Ipv6Addr>() {
+            Some(Self::V6(addr))
+        } else {
+            None
+        }
+    }
+
+    /// Parses an `IPAddress` from the raw octets of a certificate's
+    /// `iPAddress` `GeneralName` (4 bytes for IPv4, 16 for IPv6).
+    pub fn from_bytes(b: &[u8]) -> Option<Self> {
+        match b.len() {
+            4 => {
+                let octets: [u8; 4] = b.try_into().ok()?;
+                Some(Self::V4(std::net::Ipv4Addr::from(octets)))
+            }
+            16 => {
+                let octets: [u8; 16] = b.try_into().ok()?;
+                Some(Self::V6(std::net::Ipv6Addr::from(octets)))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// An `iPAddress` name constraint subtree (RFC 5280 §4.2.1.10): an address
+/// combined with a network mask of the same width, encoded back-to-back as
+/// a single OCTET STRING (8 bytes total for IPv4, 32 for IPv6).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IPConstraint {
+    V4 {
+        network: std::net::Ipv4Addr,
+        mask: std::net::Ipv4Addr,
+    },
+    V6 {
+        network: std::net::Ipv6Addr,
+        mask: std::net::Ipv6Addr,
+    },
+}
+
+impl IPConstraint {
+    /// Parses an `IPConstraint` from the raw octets of a certificate's
+    /// `iPAddress` name constraint (8 bytes for IPv4, 32 for IPv6).
+    pub fn from_bytes(b: &[u8]) -> Option<Self> {
+        match b.len() {
+            8 => {
+                let network: [u8; 4] = b[..4].try_into().ok()?;
+                let mask: [u8; 4] = b[4..].try_into().ok()?;
+                Some(Self::V4 {
+                    network: network.into(),
+                    mask: mask.into(),
+                })
+            }
+            32 => {
+                let network: [u8; 16] = b[..16].try_into().ok()?;
+                let mask: [u8; 16] = b[16..].try_into().ok()?;
+                Some(Self::V6 {
+                    network: network.into(),
+                    mask: mask.into(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if `addr` falls within this constraint: ANDing `addr`
+    /// with the mask must produce the (already-masked) network. A width
+    /// mismatch -- e.g. a v4 constraint against a v6 address -- never
+    /// matches, rather than being coerced or compared byte-for-byte.
+    pub fn matches(&self, addr: &IPAddress) -> bool {
+        match (self, addr) {
+            (Self::V4 { network, mask }, IPAddress::V4(addr)) => {
+                let mask = u32::from_be_bytes(mask.octets());
+                u32::from_be_bytes(addr.octets()) & mask == u32::from_be_bytes(network.octets()) & mask
+            }
+            (Self::V6 { network, mask }, IPAddress::V6(addr)) => {
+                let mask = u128::from_be_bytes(mask.octets());
+                u128::from_be_bytes(addr.octets()) & mask
+                    == u128::from_be_bytes(network.octets()) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// An RFC 822 email address, as asserted in a certificate's `rfc822Name`
+/// `GeneralName`.
+///
+/// Parsing splits on the *last* `@` (RFC 5321 §4.1.2 permits `@` in a
+/// quoted local part, though we don't otherwise validate the local part's
+/// grammar): the local part is preserved and compared verbatim (mailbox
+/// local parts are case-sensitive in principle), while the domain part
+/// reuses [`DNSName`]'s label validation and case-insensitive comparison.
+#[derive(Debug)]
+pub struct RFC822Name<'a> {
+    local_part: &'a str,
+    domain: DNSName<'a>,
+}
+
+impl<'a> RFC822Name<'a> {
+    pub fn new(value: &'a str) -> Option<Self> {
+        let (local_part, domain) = value.rsplit_once('@')?;
+        if local_part.is_empty() {
+            return None;
+        }
+        Some(Self {
+            local_part,
+            domain: DNSName::new(domain)?,
+        })
+    }
+}
+
+impl PartialEq for RFC822Name<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.local_part == other.local_part && self.domain == other.domain
+    }
+}
+
+/// An `rfc822Name` name constraint (RFC 5280 §4.2.1.10). Unlike
+/// [`RFC822Name`] itself, the constraint grammar also allows a bare host
+/// (matching any mailbox at exactly that host) or a host prefixed with
+/// `.` (matching any mailbox whose host is a subdomain of it, but not the
+/// host itself).
+#[derive(Debug, PartialEq)]
+pub enum RFC822Constraint<'a> {
+    Mailbox(RFC822Name<'a>),
+    Host(DNSName<'a>),
+    Subdomain(DNSName<'a>),
+}
+
+impl<'a> RFC822Constraint<'a> {
+    pub fn new(value: &'a str) -> Option<Self> {
+        if value.contains('@') {
+            RFC822Name::new(value).map(Self::Mailbox)
+        } else if let Some(domain) = value.strip_prefix('.') {
+            DNSName::new(domain).map(Self::Subdomain)
+        } else {
+            DNSName::new(value).map(Self::Host)
+        }
+    }
+
+    pub fn matches(&self, name: &RFC822Name<'_>) -> bool {
+        match self {
+            Self::Mailbox(constraint) => constraint == name,
+            Self::Host(host) => &name.domain == host,
+            Self::Subdomain(domain) => {
+                // A strict-subdomain match: walk the candidate's ancestry
+                // (skipping the domain itself, per RFC 5280) looking for
+                // `domain`.
+                let mut ancestor = name.domain.parent();
+                while let Some(current) = ancestor {
+                    if &current == domain {
+                        return true;
+                    }
+                    ancestor = current.parent();
+                }
+                false
+            }
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Asn1ReadableOrWritable, DNSName, DNSPattern, RawTlv, UnvalidatedVisibleString};
+    use super::{
+        AlgorithmIdentifier, AlgorithmParameters, Asn1ReadableOrWritable, DNSName, DNSPattern,
+        IPAddress, IPConstraint, MaskGenAlgorithm, RFC822Constraint, RFC822Name, RawTlv,
+        RsaPssParameters, UnvalidatedVisibleString, PSS_SHA1_HASH_ALG, PSS_SHA1_MASK_GEN_ALG,
+    };
+    use crate::oid;
     use asn1::Asn1Readable;
 
     #[test]
@@ -526,6 +866,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_dnsname_labels_and_trailing_n_labels() {
+        let localhost = DNSName::new("localhost").unwrap();
+        assert_eq!(localhost.labels(), 1);
+        assert_eq!(localhost.trailing_n_labels(0), None);
+        assert_eq!(localhost.trailing_n_labels(1), Some("localhost"));
+        assert_eq!(localhost.trailing_n_labels(2), None);
+
+        let name = DNSName::new("foo.bar.example.com").unwrap();
+        assert_eq!(name.labels(), 4);
+        assert_eq!(name.trailing_n_labels(1), Some("com"));
+        assert_eq!(name.trailing_n_labels(2), Some("example.com"));
+        assert_eq!(name.trailing_n_labels(3), Some("bar.example.com"));
+        assert_eq!(name.trailing_n_labels(4), Some("foo.bar.example.com"));
+        assert_eq!(name.trailing_n_labels(5), None);
+    }
+
     #[test]
     fn test_dnspattern_constructs() {
         assert_eq!(DNSPattern::new("*"), None);
@@ -568,5 +925,309 @@ mod tests {
         assert!(!any_example_com.matches(&DNSName::new("foo.bar.example.com").unwrap()));
         assert!(!any_example_com.matches(&DNSName::new("foo.bar.baz.example.com").unwrap()));
         assert!(!any_localhost.matches(&DNSName::new("localhost").unwrap()));
+
+        // Multi-level suffixes: the wildcard still only ever covers the
+        // single leftmost label, however many labels the fixed suffix has.
+        let any_foo_bar_example_com = DNSPattern::new("*.foo.bar.example.com").unwrap();
+        assert!(any_foo_bar_example_com.matches(&DNSName::new("baz.foo.bar.example.com").unwrap()));
+        assert!(any_foo_bar_example_com.matches(&DNSName::new("BAZ.foo.bar.EXAMPLE.com").unwrap()));
+        assert!(!any_foo_bar_example_com.matches(&DNSName::new("foo.bar.example.com").unwrap()));
+        assert!(!any_foo_bar_example_com
+            .matches(&DNSName::new("quux.baz.foo.bar.example.com").unwrap()));
+        // Same label count, but the fixed suffix doesn't match one-for-one.
+        assert!(!any_foo_bar_example_com.matches(&DNSName::new("baz.qux.bar.example.com").unwrap()));
+
+        // A wildcard anchored to a single-label suffix (e.g. a public
+        // suffix) only ever matches names with exactly one more label.
+        let any_dot_com = DNSPattern::new("*.com").unwrap();
+        assert!(any_dot_com.matches(&DNSName::new("example.com").unwrap()));
+        assert!(!any_dot_com.matches(&DNSName::new("com").unwrap()));
+        assert!(!any_dot_com.matches(&DNSName::new("foo.example.com").unwrap()));
+    }
+
+    #[test]
+    fn test_ipaddress_from_str() {
+        assert_eq!(
+            IPAddress::from_str("127.0.0.1").unwrap(),
+            IPAddress::V4(std::net::Ipv4Addr::new(127, 0, 0, 1))
+        );
+        assert_eq!(
+            IPAddress::from_str("::1").unwrap(),
+            IPAddress::V6(std::net::Ipv6Addr::LOCALHOST)
+        );
+        assert_eq!(IPAddress::from_str("not-an-ip"), None);
+    }
+
+    #[test]
+    fn test_ipaddress_from_bytes() {
+        assert_eq!(
+            IPAddress::from_bytes(&[127, 0, 0, 1]).unwrap(),
+            IPAddress::V4(std::net::Ipv4Addr::new(127, 0, 0, 1))
+        );
+        assert_eq!(IPAddress::from_bytes(&[1, 2, 3]), None);
+    }
+
+    #[test]
+    fn test_ipconstraint_from_bytes() {
+        assert_eq!(IPConstraint::from_bytes(&[1, 2, 3]), None);
+
+        let v4 = IPConstraint::from_bytes(&[192, 168, 0, 0, 255, 255, 0, 0]).unwrap();
+        assert_eq!(
+            v4,
+            IPConstraint::V4 {
+                network: std::net::Ipv4Addr::new(192, 168, 0, 0),
+                mask: std::net::Ipv4Addr::new(255, 255, 0, 0),
+            }
+        );
+
+        let v6_bytes = [
+            // network: 2001:db8:: /32
+            0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            // mask: ffff:ffff:: (/32)
+            0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        assert!(IPConstraint::from_bytes(&v6_bytes).is_some());
+    }
+
+    #[test]
+    fn test_ipconstraint_matches() {
+        // 192.168.0.0/16
+        let v4 = IPConstraint::from_bytes(&[192, 168, 0, 0, 255, 255, 0, 0]).unwrap();
+        assert!(v4.matches(&IPAddress::from_str("192.168.1.1").unwrap()));
+        assert!(v4.matches(&IPAddress::from_str("192.168.0.0").unwrap()));
+        assert!(!v4.matches(&IPAddress::from_str("192.169.0.1").unwrap()));
+        // A v4 constraint never matches a v6 address, regardless of octets.
+        assert!(!v4.matches(&IPAddress::from_str("::1").unwrap()));
+
+        // 2001:db8::/32
+        let mut v6_bytes = [0u8; 32];
+        v6_bytes[0..4].copy_from_slice(&[0x20, 0x01, 0x0d, 0xb8]);
+        v6_bytes[16..20].copy_from_slice(&[0xff, 0xff, 0xff, 0xff]);
+        let v6 = IPConstraint::from_bytes(&v6_bytes).unwrap();
+        assert!(v6.matches(&IPAddress::from_str("2001:db8::1").unwrap()));
+        assert!(!v6.matches(&IPAddress::from_str("2001:db9::1").unwrap()));
+        // A v6 constraint never matches a v4 address.
+        assert!(!v6.matches(&IPAddress::from_str("127.0.0.1").unwrap()));
+    }
+
+    #[test]
+    fn test_rfc822name_constructs() {
+        assert_eq!(RFC822Name::new(""), None);
+        assert_eq!(RFC822Name::new("no-at-sign.example.com"), None);
+        assert_eq!(RFC822Name::new("@example.com"), None);
+        assert_eq!(RFC822Name::new("user@"), None);
+        assert_eq!(RFC822Name::new("user@!bad!"), None);
+
+        assert!(RFC822Name::new("user@example.com").is_some());
+        // The last `@` is the split point.
+        assert!(RFC822Name::new("us@er@example.com").is_some());
+    }
+
+    #[test]
+    fn test_rfc822name_equality() {
+        // The local part is case-sensitive...
+        assert_ne!(
+            RFC822Name::new("User@example.com").unwrap(),
+            RFC822Name::new("user@example.com").unwrap()
+        );
+        // ...but the domain part is not.
+        assert_eq!(
+            RFC822Name::new("user@EXAMPLE.com").unwrap(),
+            RFC822Name::new("user@example.com").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rfc822constraint_constructs() {
+        assert_eq!(RFC822Constraint::new(""), None);
+        assert_eq!(RFC822Constraint::new("user@"), None);
+        assert_eq!(RFC822Constraint::new("."), None);
+
+        assert_eq!(
+            RFC822Constraint::new("user@example.com").unwrap(),
+            RFC822Constraint::Mailbox(RFC822Name::new("user@example.com").unwrap())
+        );
+        assert_eq!(
+            RFC822Constraint::new("example.com").unwrap(),
+            RFC822Constraint::Host(DNSName::new("example.com").unwrap())
+        );
+        assert_eq!(
+            RFC822Constraint::new(".example.com").unwrap(),
+            RFC822Constraint::Subdomain(DNSName::new("example.com").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_rfc822constraint_matches() {
+        // A mailbox constraint matches only that exact mailbox.
+        let mailbox = RFC822Constraint::new("user@example.com").unwrap();
+        assert!(mailbox.matches(&RFC822Name::new("user@example.com").unwrap()));
+        assert!(mailbox.matches(&RFC822Name::new("user@EXAMPLE.com").unwrap()));
+        assert!(!mailbox.matches(&RFC822Name::new("other@example.com").unwrap()));
+
+        // A bare-host constraint matches any mailbox at exactly that host.
+        let host = RFC822Constraint::new("example.com").unwrap();
+        assert!(host.matches(&RFC822Name::new("alice@example.com").unwrap()));
+        assert!(host.matches(&RFC822Name::new("bob@EXAMPLE.com").unwrap()));
+        assert!(!host.matches(&RFC822Name::new("alice@host.example.com").unwrap()));
+
+        // A `.`-prefixed constraint matches subdomains, but not the domain
+        // itself.
+        let subdomain = RFC822Constraint::new(".example.com").unwrap();
+        assert!(subdomain.matches(&RFC822Name::new("alice@host.example.com").unwrap()));
+        assert!(subdomain.matches(&RFC822Name::new("alice@a.b.example.com").unwrap()));
+        assert!(!subdomain.matches(&RFC822Name::new("alice@example.com").unwrap()));
+        assert!(!subdomain.matches(&RFC822Name::new("alice@notexample.com").unwrap()));
+    }
+
+    fn sha256_hash_alg() -> AlgorithmIdentifier<'static> {
+        AlgorithmIdentifier {
+            oid: asn1::DefinedByMarker::marker(),
+            params: AlgorithmParameters::Sha256(Some(())),
+        }
+    }
+
+    fn sha384_hash_alg() -> AlgorithmIdentifier<'static> {
+        AlgorithmIdentifier {
+            oid: asn1::DefinedByMarker::marker(),
+            params: AlgorithmParameters::Sha384(Some(())),
+        }
+    }
+
+    #[test]
+    fn test_algorithm_identifier_sha256_null_and_absent_round_trip() {
+        // RFC 4055 §2.1: NULL and absent parameters are both legal and
+        // equivalent, so a SHA-256 `AlgorithmIdentifier` must parse either
+        // way, even though we only ever write the explicit-NULL form.
+        let with_null = AlgorithmIdentifier {
+            oid: asn1::DefinedByMarker::marker(),
+            params: AlgorithmParameters::Sha256(Some(())),
+        };
+        let with_null_der = asn1::write_single(&with_null).unwrap();
+        let parsed_with_null: AlgorithmIdentifier<'_> =
+            asn1::parse_single(&with_null_der).unwrap();
+        assert_eq!(parsed_with_null.params, AlgorithmParameters::Sha256(Some(())));
+
+        let without_null = AlgorithmIdentifier {
+            oid: asn1::DefinedByMarker::marker(),
+            params: AlgorithmParameters::Sha256(None),
+        };
+        let without_null_der = asn1::write_single(&without_null).unwrap();
+        assert!(without_null_der.len() < with_null_der.len());
+        let parsed_without_null: AlgorithmIdentifier<'_> =
+            asn1::parse_single(&without_null_der).unwrap();
+        assert_eq!(parsed_without_null.params, AlgorithmParameters::Sha256(None));
+    }
+
+    #[test]
+    fn test_rsa_pss_parameters_as_verification_config_default_is_valid() {
+        // The all-defaults encoding (SHA-1 / MGF1-SHA1 / salt 20 / trailer 1)
+        // is internally consistent and must resolve.
+        let params = RsaPssParameters {
+            hash_algorithm: PSS_SHA1_HASH_ALG,
+            mask_gen_algorithm: PSS_SHA1_MASK_GEN_ALG,
+            salt_length: 20,
+            _trailer_field: 1,
+        };
+        let (hash, salt_length) = params.as_verification_config().unwrap();
+        assert_eq!(hash, AlgorithmParameters::Sha1(Some(())));
+        assert_eq!(salt_length, 20);
+    }
+
+    #[test]
+    fn test_rsa_pss_parameters_as_verification_config_sha256() {
+        let params = RsaPssParameters {
+            hash_algorithm: sha256_hash_alg(),
+            mask_gen_algorithm: MaskGenAlgorithm {
+                oid: oid::MGF1_OID,
+                params: sha256_hash_alg(),
+            },
+            salt_length: 32,
+            _trailer_field: 1,
+        };
+        let (hash, salt_length) = params.as_verification_config().unwrap();
+        assert_eq!(hash, AlgorithmParameters::Sha256(Some(())));
+        assert_eq!(salt_length, 32);
+    }
+
+    #[test]
+    fn test_rsa_pss_parameters_as_verification_config_tolerates_absent_mgf1_null() {
+        // LibreSSL omits the NULL parameters on the MGF1 inner hash entirely
+        // rather than encoding them explicitly; RFC 4055 §2.1 says this is
+        // equivalent to an explicit NULL and must still be accepted.
+        let params = RsaPssParameters {
+            hash_algorithm: sha256_hash_alg(),
+            mask_gen_algorithm: MaskGenAlgorithm {
+                oid: oid::MGF1_OID,
+                params: AlgorithmIdentifier {
+                    oid: asn1::DefinedByMarker::marker(),
+                    params: AlgorithmParameters::Sha256(None),
+                },
+            },
+            salt_length: 32,
+            _trailer_field: 1,
+        };
+        let (hash, salt_length) = params.as_verification_config().unwrap();
+        assert_eq!(hash, AlgorithmParameters::Sha256(Some(())));
+        assert_eq!(salt_length, 32);
+    }
+
+    #[test]
+    fn test_rsa_pss_parameters_as_verification_config_rejects_non_mgf1_oid() {
+        let params = RsaPssParameters {
+            hash_algorithm: sha256_hash_alg(),
+            mask_gen_algorithm: MaskGenAlgorithm {
+                oid: oid::SHA256_OID,
+                params: sha256_hash_alg(),
+            },
+            salt_length: 32,
+            _trailer_field: 1,
+        };
+        assert!(params.as_verification_config().is_none());
+    }
+
+    #[test]
+    fn test_rsa_pss_parameters_as_verification_config_rejects_mismatched_inner_hash() {
+        // `mask_gen_algorithm`'s inner hash (SHA-256) disagrees with
+        // `hash_algorithm` (SHA-384); RFC 4055 only defines combinations
+        // where the two agree.
+        let params = RsaPssParameters {
+            hash_algorithm: sha384_hash_alg(),
+            mask_gen_algorithm: MaskGenAlgorithm {
+                oid: oid::MGF1_OID,
+                params: sha256_hash_alg(),
+            },
+            salt_length: 48,
+            _trailer_field: 1,
+        };
+        assert!(params.as_verification_config().is_none());
+    }
+
+    #[test]
+    fn test_rsa_pss_parameters_as_verification_config_rejects_non_default_trailer_field() {
+        let params = RsaPssParameters {
+            hash_algorithm: sha256_hash_alg(),
+            mask_gen_algorithm: MaskGenAlgorithm {
+                oid: oid::MGF1_OID,
+                params: sha256_hash_alg(),
+            },
+            salt_length: 32,
+            _trailer_field: 2,
+        };
+        assert!(params.as_verification_config().is_none());
+    }
+
+    #[test]
+    fn test_rsa_pss_parameters_as_verification_config_rejects_inconsistent_salt_length() {
+        let params = RsaPssParameters {
+            hash_algorithm: sha256_hash_alg(),
+            mask_gen_algorithm: MaskGenAlgorithm {
+                oid: oid::MGF1_OID,
+                params: sha256_hash_alg(),
+            },
+            salt_length: 20,
+            _trailer_field: 1,
+        };
+        assert!(params.as_verification_config().is_none());
     }
 }
@@ -6,7 +6,45 @@ use crate::error::{CryptographyError, CryptographyResult};
 use crate::exceptions;
 use cryptography_x509::{common, oid};
 
-#[derive(Debug, PartialEq)]
+/// A lazily-populated handle to a Python type object, cached for the
+/// lifetime of the process. `identify_key_type`/`identify_public_key_type`/
+/// `identify_hash_type` run on every signing and verification operation, so
+/// avoiding a fresh `import` + `getattr` on each call matters for bulk
+/// verification (e.g. a large chain or CRL).
+struct CachedType(pyo3::sync::GILOnceCell<pyo3::Py<pyo3::types::PyType>>);
+
+impl CachedType {
+    const fn new() -> Self {
+        Self(pyo3::sync::GILOnceCell::new())
+    }
+
+    fn get<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        module_name: &str,
+        attr_name: &str,
+    ) -> pyo3::PyResult<&'p pyo3::types::PyType> {
+        let ty = self.0.get_or_try_init(py, || -> pyo3::PyResult<_> {
+            py.import(module_name)?.getattr(attr_name)?.extract()
+        })?;
+        Ok(ty.as_ref(py))
+    }
+}
+
+static RSA_PRIVATE_KEY_TYPE: CachedType = CachedType::new();
+static DSA_PRIVATE_KEY_TYPE: CachedType = CachedType::new();
+static EC_PRIVATE_KEY_TYPE: CachedType = CachedType::new();
+static ED25519_PRIVATE_KEY_TYPE: CachedType = CachedType::new();
+static ED448_PRIVATE_KEY_TYPE: CachedType = CachedType::new();
+static RSA_PUBLIC_KEY_TYPE: CachedType = CachedType::new();
+static DSA_PUBLIC_KEY_TYPE: CachedType = CachedType::new();
+static EC_PUBLIC_KEY_TYPE: CachedType = CachedType::new();
+static ED25519_PUBLIC_KEY_TYPE: CachedType = CachedType::new();
+static ED448_PUBLIC_KEY_TYPE: CachedType = CachedType::new();
+static HASH_ALGORITHM_TYPE: CachedType = CachedType::new();
+static PSS_TYPE: CachedType = CachedType::new();
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) enum KeyType {
     Rsa,
     Dsa,
@@ -15,65 +53,90 @@ pub(crate) enum KeyType {
     Ed448,
 }
 
-#[derive(Debug, PartialEq)]
-enum HashType {
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum HashType {
     None,
+    // Verify-only: signatures using SHA-1 can be checked against existing
+    // legacy certificates/CRLs/OCSP responses, but nothing in this module
+    // will produce a new SHA-1 signature (`identify_hash_type` never returns
+    // it and `identify_alg_params_for_hash_type` rejects it).
+    Sha1,
     Sha224,
     Sha256,
     Sha384,
     Sha512,
+    Sha512_224,
+    Sha512_256,
     Sha3_224,
     Sha3_256,
     Sha3_384,
     Sha3_512,
 }
 
+/// A fully-resolved signature scheme: which key family, which hash, and --
+/// for RSA -- which padding to use. Building one of these up front, whether
+/// from the Python-level `hash_algorithm`/`rsa_padding` arguments used when
+/// signing or from the ASN.1 `AlgorithmParameters` used when verifying,
+/// gives `sign_with_scheme` and `verify_with_scheme` a single place to own
+/// the per-algorithm padding/hash-object construction, instead of
+/// duplicating it across `compute_signature_algorithm`, `sign_data`, and
+/// `verify_signature_with_oid`.
+#[derive(Debug, PartialEq)]
+enum SignatureScheme {
+    Ed25519,
+    Ed448,
+    Ec(HashType),
+    Dsa(HashType),
+    RsaPkcs1v15(HashType),
+    RsaPss {
+        hash: HashType,
+        mgf_hash: HashType,
+        salt_length: u16,
+    },
+}
+
+impl SignatureScheme {
+    fn key_type(&self) -> KeyType {
+        match self {
+            SignatureScheme::Ed25519 => KeyType::Ed25519,
+            SignatureScheme::Ed448 => KeyType::Ed448,
+            SignatureScheme::Ec(_) => KeyType::Ec,
+            SignatureScheme::Dsa(_) => KeyType::Dsa,
+            SignatureScheme::RsaPkcs1v15(_) | SignatureScheme::RsaPss { .. } => KeyType::Rsa,
+        }
+    }
+}
+
 fn identify_key_type(py: pyo3::Python<'_>, private_key: &pyo3::PyAny) -> pyo3::PyResult<KeyType> {
-    let rsa_private_key: &pyo3::types::PyType = py
-        .import(pyo3::intern!(
-            py,
-            "cryptography.hazmat.primitives.asymmetric.rsa"
-        ))?
-        .getattr(pyo3::intern!(py, "RSAPrivateKey"))?
-        .extract()?;
-    let dsa_key_type: &pyo3::types::PyType = py
-        .import(pyo3::intern!(
-            py,
-            "cryptography.hazmat.primitives.asymmetric.dsa"
-        ))?
-        .getattr(pyo3::intern!(py, "DSAPrivateKey"))?
-        .extract()?;
-    let ec_key_type: &pyo3::types::PyType = py
-        .import(pyo3::intern!(
-            py,
-            "cryptography.hazmat.primitives.asymmetric.ec"
-        ))?
-        .getattr(pyo3::intern!(py, "EllipticCurvePrivateKey"))?
-        .extract()?;
-    let ed25519_key_type: &pyo3::types::PyType = py
-        .import(pyo3::intern!(
-            py,
-            "cryptography.hazmat.primitives.asymmetric.ed25519"
-        ))?
-        .getattr(pyo3::intern!(py, "Ed25519PrivateKey"))?
-        .extract()?;
-    let ed448_key_type: &pyo3::types::PyType = py
-        .import(pyo3::intern!(
-            py,
-            "cryptography.hazmat.primitives.asymmetric.ed448"
-        ))?
-        .getattr(pyo3::intern!(py, "Ed448PrivateKey"))?
-        .extract()?;
-
-    if private_key.is_instance(rsa_private_key)? {
+    if private_key.is_instance(RSA_PRIVATE_KEY_TYPE.get(
+        py,
+        "cryptography.hazmat.primitives.asymmetric.rsa",
+        "RSAPrivateKey",
+    )?)? {
         Ok(KeyType::Rsa)
-    } else if private_key.is_instance(dsa_key_type)? {
+    } else if private_key.is_instance(DSA_PRIVATE_KEY_TYPE.get(
+        py,
+        "cryptography.hazmat.primitives.asymmetric.dsa",
+        "DSAPrivateKey",
+    )?)? {
         Ok(KeyType::Dsa)
-    } else if private_key.is_instance(ec_key_type)? {
+    } else if private_key.is_instance(EC_PRIVATE_KEY_TYPE.get(
+        py,
+        "cryptography.hazmat.primitives.asymmetric.ec",
+        "EllipticCurvePrivateKey",
+    )?)? {
         Ok(KeyType::Ec)
-    } else if private_key.is_instance(ed25519_key_type)? {
+    } else if private_key.is_instance(ED25519_PRIVATE_KEY_TYPE.get(
+        py,
+        "cryptography.hazmat.primitives.asymmetric.ed25519",
+        "Ed25519PrivateKey",
+    )?)? {
         Ok(KeyType::Ed25519)
-    } else if private_key.is_instance(ed448_key_type)? {
+    } else if private_key.is_instance(ED448_PRIVATE_KEY_TYPE.get(
+        py,
+        "cryptography.hazmat.primitives.asymmetric.ed448",
+        "Ed448PrivateKey",
+    )?)? {
         Ok(KeyType::Ed448)
     } else {
         Err(pyo3::exceptions::PyTypeError::new_err(
@@ -90,10 +153,11 @@ fn identify_hash_type(
         return Ok(HashType::None);
     }
 
-    let hash_algorithm_type: &pyo3::types::PyType = py
-        .import(pyo3::intern!(py, "cryptography.hazmat.primitives.hashes"))?
-        .getattr(pyo3::intern!(py, "HashAlgorithm"))?
-        .extract()?;
+    let hash_algorithm_type = HASH_ALGORITHM_TYPE.get(
+        py,
+        "cryptography.hazmat.primitives.hashes",
+        "HashAlgorithm",
+    )?;
     if !hash_algorithm.is_instance(hash_algorithm_type)? {
         return Err(pyo3::exceptions::PyTypeError::new_err(
             "Algorithm must be a registered hash algorithm.",
@@ -108,6 +172,8 @@ fn identify_hash_type(
         "sha256" => Ok(HashType::Sha256),
         "sha384" => Ok(HashType::Sha384),
         "sha512" => Ok(HashType::Sha512),
+        "sha512-224" => Ok(HashType::Sha512_224),
+        "sha512-256" => Ok(HashType::Sha512_256),
         "sha3-224" => Ok(HashType::Sha3_224),
         "sha3-256" => Ok(HashType::Sha3_256),
         "sha3-384" => Ok(HashType::Sha3_384),
@@ -119,40 +185,141 @@ fn identify_hash_type(
     }
 }
 
-pub(crate) fn compute_signature_algorithm<'p>(
-    py: pyo3::Python<'p>,
-    private_key: &'p pyo3::PyAny,
-    hash_algorithm: &'p pyo3::PyAny,
-    rsa_padding: &'p pyo3::PyAny,
-) -> pyo3::PyResult<common::AlgorithmIdentifier<'static>> {
-    let key_type = identify_key_type(py, private_key)?;
-    let hash_type = identify_hash_type(py, hash_algorithm)?;
+/// Resolves the `(key_type, hash_algorithm, rsa_padding)` arguments accepted
+/// by the signing APIs down to a single `SignatureScheme`, detecting RSA-PSS
+/// from `rsa_padding` along the way.
+fn scheme_for_signing(
+    py: pyo3::Python<'_>,
+    key_type: KeyType,
+    hash_type: HashType,
+    rsa_padding: &pyo3::PyAny,
+) -> pyo3::PyResult<SignatureScheme> {
+    match key_type {
+        KeyType::Ed25519 => {
+            if hash_type != HashType::None {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "Algorithm must be None when signing via ed25519 or ed448",
+                ));
+            }
+            Ok(SignatureScheme::Ed25519)
+        }
+        KeyType::Ed448 => {
+            if hash_type != HashType::None {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "Algorithm must be None when signing via ed25519 or ed448",
+                ));
+            }
+            Ok(SignatureScheme::Ed448)
+        }
+        KeyType::Ec => {
+            if hash_type == HashType::None {
+                return Err(pyo3::exceptions::PyTypeError::new_err(
+                    "Algorithm must be a registered hash algorithm, not None.",
+                ));
+            }
+            Ok(SignatureScheme::Ec(hash_type))
+        }
+        KeyType::Dsa => match hash_type {
+            HashType::None => Err(pyo3::exceptions::PyTypeError::new_err(
+                "Algorithm must be a registered hash algorithm, not None.",
+            )),
+            HashType::Sha3_224 | HashType::Sha3_256 | HashType::Sha3_384 | HashType::Sha3_512 => {
+                Err(exceptions::UnsupportedAlgorithm::new_err(
+                    "SHA3 hashes are not supported with DSA keys",
+                ))
+            }
+            _ => Ok(SignatureScheme::Dsa(hash_type)),
+        },
+        KeyType::Rsa => {
+            if hash_type == HashType::None {
+                return Err(pyo3::exceptions::PyTypeError::new_err(
+                    "Algorithm must be a registered hash algorithm, not None.",
+                ));
+            }
+            let pss_type =
+                PSS_TYPE.get(py, "cryptography.hazmat.primitives.asymmetric.padding", "PSS")?;
+            if !rsa_padding.is_none() && rsa_padding.is_instance(pss_type)? {
+                let salt_length = rsa_padding.getattr("_salt_length")?.extract::<u16>()?;
+                let py_mgf_alg = rsa_padding
+                    .getattr(pyo3::intern!(py, "_mgf"))?
+                    .getattr(pyo3::intern!(py, "_algorithm"))?;
+                let mgf_hash_type = identify_hash_type(py, py_mgf_alg)?;
+                Ok(SignatureScheme::RsaPss {
+                    hash: hash_type,
+                    mgf_hash: mgf_hash_type,
+                    salt_length,
+                })
+            } else {
+                Ok(SignatureScheme::RsaPkcs1v15(hash_type))
+            }
+        }
+    }
+}
 
-    let pss_type: &pyo3::types::PyType = py
-        .import(pyo3::intern!(
-            py,
-            "cryptography.hazmat.primitives.asymmetric.padding"
-        ))?
-        .getattr(pyo3::intern!(py, "PSS"))?
-        .extract()?;
-    // If this is RSA-PSS we need to compute the signature algorithm from the
-    // parameters provided in rsa_padding.
-    if !rsa_padding.is_none() && rsa_padding.is_instance(pss_type)? {
-        let hash_alg_params = identify_alg_params_for_hash_type(hash_type)?;
-        let hash_algorithm = common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: hash_alg_params,
-        };
-        let salt_length = rsa_padding.getattr("_salt_length")?.extract::<u16>()?;
-        let py_mgf_alg = rsa_padding
-            .getattr(pyo3::intern!(py, "_mgf"))?
-            .getattr(pyo3::intern!(py, "_algorithm"))?;
-        let mgf_hash_type = identify_hash_type(py, py_mgf_alg)?;
-        let mgf_alg = common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: identify_alg_params_for_hash_type(mgf_hash_type)?,
-        };
-        let params =
+fn algorithm_identifier_for_scheme(
+    scheme: &SignatureScheme,
+) -> pyo3::PyResult<common::AlgorithmIdentifier<'static>> {
+    let params = match *scheme {
+        SignatureScheme::Ed25519 => common::AlgorithmParameters::Ed25519,
+        SignatureScheme::Ed448 => common::AlgorithmParameters::Ed448,
+        SignatureScheme::Ec(hash_type) => match hash_type {
+            HashType::Sha224 => common::AlgorithmParameters::EcDsaWithSha224(Some(())),
+            HashType::Sha256 => common::AlgorithmParameters::EcDsaWithSha256(Some(())),
+            HashType::Sha384 => common::AlgorithmParameters::EcDsaWithSha384(Some(())),
+            HashType::Sha512 => common::AlgorithmParameters::EcDsaWithSha512(Some(())),
+            HashType::Sha512_224 => common::AlgorithmParameters::EcDsaWithSha512_224(Some(())),
+            HashType::Sha512_256 => common::AlgorithmParameters::EcDsaWithSha512_256(Some(())),
+            HashType::Sha3_224 => common::AlgorithmParameters::EcDsaWithSha3_224,
+            HashType::Sha3_256 => common::AlgorithmParameters::EcDsaWithSha3_256,
+            HashType::Sha3_384 => common::AlgorithmParameters::EcDsaWithSha3_384,
+            HashType::Sha3_512 => common::AlgorithmParameters::EcDsaWithSha3_512,
+            HashType::Sha1 | HashType::None => {
+                return Err(pyo3::exceptions::PyTypeError::new_err(
+                    "Algorithm must be a registered hash algorithm, not None.",
+                ))
+            }
+        },
+        SignatureScheme::Dsa(hash_type) => match hash_type {
+            HashType::Sha224 => common::AlgorithmParameters::DsaWithSha224,
+            HashType::Sha256 => common::AlgorithmParameters::DsaWithSha256,
+            HashType::Sha384 => common::AlgorithmParameters::DsaWithSha384,
+            HashType::Sha512 => common::AlgorithmParameters::DsaWithSha512,
+            _ => {
+                return Err(exceptions::UnsupportedAlgorithm::new_err(
+                    "SHA3 and SHA-512/t hashes are not supported with DSA keys",
+                ))
+            }
+        },
+        SignatureScheme::RsaPkcs1v15(hash_type) => match hash_type {
+            HashType::Sha224 => common::AlgorithmParameters::RsaWithSha224(Some(())),
+            HashType::Sha256 => common::AlgorithmParameters::RsaWithSha256(Some(())),
+            HashType::Sha384 => common::AlgorithmParameters::RsaWithSha384(Some(())),
+            HashType::Sha512 => common::AlgorithmParameters::RsaWithSha512(Some(())),
+            HashType::Sha512_224 => common::AlgorithmParameters::RsaWithSha512_224(Some(())),
+            HashType::Sha512_256 => common::AlgorithmParameters::RsaWithSha512_256(Some(())),
+            HashType::Sha3_224 => common::AlgorithmParameters::RsaWithSha3_224(Some(())),
+            HashType::Sha3_256 => common::AlgorithmParameters::RsaWithSha3_256(Some(())),
+            HashType::Sha3_384 => common::AlgorithmParameters::RsaWithSha3_384(Some(())),
+            HashType::Sha3_512 => common::AlgorithmParameters::RsaWithSha3_512(Some(())),
+            HashType::Sha1 | HashType::None => {
+                return Err(pyo3::exceptions::PyTypeError::new_err(
+                    "Algorithm must be a registered hash algorithm, not None.",
+                ))
+            }
+        },
+        SignatureScheme::RsaPss {
+            hash,
+            mgf_hash,
+            salt_length,
+        } => {
+            let hash_algorithm = common::AlgorithmIdentifier {
+                oid: asn1::DefinedByMarker::marker(),
+                params: identify_alg_params_for_hash_type(hash)?,
+            };
+            let mgf_alg = common::AlgorithmIdentifier {
+                oid: asn1::DefinedByMarker::marker(),
+                params: identify_alg_params_for_hash_type(mgf_hash)?,
+            };
             common::AlgorithmParameters::RsaPss(Some(Box::new(common::RsaPssParameters {
                 hash_algorithm,
                 mask_gen_algorithm: common::MaskGenAlgorithm {
@@ -161,177 +328,120 @@ pub(crate) fn compute_signature_algorithm<'p>(
                 },
                 salt_length,
                 _trailer_field: 1,
-            })));
-
-        return Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params,
-        });
-    }
-    // It's not an RSA PSS signature, so we compute the signature algorithm from
-    // the union of key type and hash type.
-    match (key_type, hash_type) {
-        (KeyType::Ed25519, HashType::None) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::Ed25519,
-        }),
-        (KeyType::Ed448, HashType::None) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::Ed448,
-        }),
-        (KeyType::Ed25519 | KeyType::Ed448, _) => Err(pyo3::exceptions::PyValueError::new_err(
-            "Algorithm must be None when signing via ed25519 or ed448",
-        )),
-
-        (KeyType::Ec, HashType::Sha224) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::EcDsaWithSha224,
-        }),
-        (KeyType::Ec, HashType::Sha256) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::EcDsaWithSha256,
-        }),
-        (KeyType::Ec, HashType::Sha384) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::EcDsaWithSha384,
-        }),
-        (KeyType::Ec, HashType::Sha512) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::EcDsaWithSha512,
-        }),
-        (KeyType::Ec, HashType::Sha3_224) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::EcDsaWithSha3_224,
-        }),
-        (KeyType::Ec, HashType::Sha3_256) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::EcDsaWithSha3_256,
-        }),
-        (KeyType::Ec, HashType::Sha3_384) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::EcDsaWithSha3_384,
-        }),
-        (KeyType::Ec, HashType::Sha3_512) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::EcDsaWithSha3_512,
-        }),
-
-        (KeyType::Rsa, HashType::Sha224) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::RsaWithSha224(Some(())),
-        }),
-        (KeyType::Rsa, HashType::Sha256) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::RsaWithSha256(Some(())),
-        }),
-        (KeyType::Rsa, HashType::Sha384) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::RsaWithSha384(Some(())),
-        }),
-        (KeyType::Rsa, HashType::Sha512) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::RsaWithSha512(Some(())),
-        }),
-        (KeyType::Rsa, HashType::Sha3_224) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::RsaWithSha3_224(Some(())),
-        }),
-        (KeyType::Rsa, HashType::Sha3_256) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::RsaWithSha3_256(Some(())),
-        }),
-        (KeyType::Rsa, HashType::Sha3_384) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::RsaWithSha3_384(Some(())),
-        }),
-        (KeyType::Rsa, HashType::Sha3_512) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::RsaWithSha3_512(Some(())),
-        }),
-
-        (KeyType::Dsa, HashType::Sha224) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::DsaWithSha224,
-        }),
-        (KeyType::Dsa, HashType::Sha256) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::DsaWithSha256,
-        }),
-        (KeyType::Dsa, HashType::Sha384) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::DsaWithSha384,
-        }),
-        (KeyType::Dsa, HashType::Sha512) => Ok(common::AlgorithmIdentifier {
-            oid: asn1::DefinedByMarker::marker(),
-            params: common::AlgorithmParameters::DsaWithSha512,
-        }),
-        (
-            KeyType::Dsa,
-            HashType::Sha3_224 | HashType::Sha3_256 | HashType::Sha3_384 | HashType::Sha3_512,
-        ) => Err(exceptions::UnsupportedAlgorithm::new_err(
-            "SHA3 hashes are not supported with DSA keys",
-        )),
-        (_, HashType::None) => Err(pyo3::exceptions::PyTypeError::new_err(
-            "Algorithm must be a registered hash algorithm, not None.",
-        )),
-    }
+            })))
+        }
+    };
+    Ok(common::AlgorithmIdentifier {
+        oid: asn1::DefinedByMarker::marker(),
+        params,
+    })
 }
 
-pub(crate) fn sign_data<'p>(
+pub(crate) fn compute_signature_algorithm<'p>(
     py: pyo3::Python<'p>,
     private_key: &'p pyo3::PyAny,
     hash_algorithm: &'p pyo3::PyAny,
     rsa_padding: &'p pyo3::PyAny,
-    data: &[u8],
-) -> pyo3::PyResult<&'p [u8]> {
+) -> pyo3::PyResult<common::AlgorithmIdentifier<'static>> {
     let key_type = identify_key_type(py, private_key)?;
+    let hash_type = identify_hash_type(py, hash_algorithm)?;
+    let scheme = scheme_for_signing(py, key_type, hash_type, rsa_padding)?;
+    algorithm_identifier_for_scheme(&scheme)
+}
 
-    let signature = match key_type {
-        KeyType::Ed25519 | KeyType::Ed448 => {
+fn py_hash_object_for_type(
+    py: pyo3::Python<'_>,
+    hash_type: HashType,
+) -> pyo3::PyResult<&pyo3::PyAny> {
+    let name = py_hash_name_from_hash_type(hash_type).ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err("internal error: hash type has no Python name")
+    })?;
+    let hashes = py.import(pyo3::intern!(py, "cryptography.hazmat.primitives.hashes"))?;
+    hashes.getattr(name)?.call0()
+}
+
+fn sign_with_scheme<'p>(
+    py: pyo3::Python<'p>,
+    private_key: &'p pyo3::PyAny,
+    scheme: &SignatureScheme,
+    data: &[u8],
+) -> pyo3::PyResult<&'p [u8]> {
+    let signature = match scheme {
+        SignatureScheme::Ed25519 | SignatureScheme::Ed448 => {
             private_key.call_method1(pyo3::intern!(py, "sign"), (data,))?
         }
-        KeyType::Ec => {
+        SignatureScheme::Ec(hash_type) => {
             let ec_mod = py.import(pyo3::intern!(
                 py,
                 "cryptography.hazmat.primitives.asymmetric.ec"
             ))?;
+            let hash_obj = py_hash_object_for_type(py, *hash_type)?;
             let ecdsa = ec_mod
                 .getattr(pyo3::intern!(py, "ECDSA"))?
-                .call1((hash_algorithm,))?;
+                .call1((hash_obj,))?;
             private_key.call_method1(pyo3::intern!(py, "sign"), (data, ecdsa))?
         }
-        KeyType::Rsa => {
-            if rsa_padding.is_none() {
-                let padding_mod = py.import(pyo3::intern!(
-                    py,
-                    "cryptography.hazmat.primitives.asymmetric.padding"
-                ))?;
-                let pkcs1v15 = padding_mod
-                    .getattr(pyo3::intern!(py, "PKCS1v15"))?
-                    .call0()?;
-                private_key
-                    .call_method1(pyo3::intern!(py, "sign"), (data, pkcs1v15, hash_algorithm))?
-            } else {
-                private_key.call_method1(
-                    pyo3::intern!(py, "sign"),
-                    (data, rsa_padding, hash_algorithm),
-                )?
-            }
+        SignatureScheme::Dsa(hash_type) => {
+            let hash_obj = py_hash_object_for_type(py, *hash_type)?;
+            private_key.call_method1(pyo3::intern!(py, "sign"), (data, hash_obj))?
         }
-        KeyType::Dsa => {
-            private_key.call_method1(pyo3::intern!(py, "sign"), (data, hash_algorithm))?
+        SignatureScheme::RsaPkcs1v15(hash_type) => {
+            let hash_obj = py_hash_object_for_type(py, *hash_type)?;
+            let padding_mod = py.import(pyo3::intern!(
+                py,
+                "cryptography.hazmat.primitives.asymmetric.padding"
+            ))?;
+            let pkcs1v15 = padding_mod
+                .getattr(pyo3::intern!(py, "PKCS1v15"))?
+                .call0()?;
+            private_key.call_method1(pyo3::intern!(py, "sign"), (data, pkcs1v15, hash_obj))?
+        }
+        SignatureScheme::RsaPss {
+            hash,
+            mgf_hash,
+            salt_length,
+        } => {
+            let hash_obj = py_hash_object_for_type(py, *hash)?;
+            let mgf_hash_obj = py_hash_object_for_type(py, *mgf_hash)?;
+            let padding_mod = py.import(pyo3::intern!(
+                py,
+                "cryptography.hazmat.primitives.asymmetric.padding"
+            ))?;
+            let mgf1 = padding_mod
+                .getattr(pyo3::intern!(py, "MGF1"))?
+                .call1((mgf_hash_obj,))?;
+            let pss = padding_mod
+                .getattr(pyo3::intern!(py, "PSS"))?
+                .call1((mgf1, *salt_length))?;
+            private_key.call_method1(pyo3::intern!(py, "sign"), (data, pss, hash_obj))?
         }
     };
     signature.extract()
 }
 
+pub(crate) fn sign_data<'p>(
+    py: pyo3::Python<'p>,
+    private_key: &'p pyo3::PyAny,
+    hash_algorithm: &'p pyo3::PyAny,
+    rsa_padding: &'p pyo3::PyAny,
+    data: &[u8],
+) -> pyo3::PyResult<&'p [u8]> {
+    let key_type = identify_key_type(py, private_key)?;
+    let hash_type = identify_hash_type(py, hash_algorithm)?;
+    let scheme = scheme_for_signing(py, key_type, hash_type, rsa_padding)?;
+    sign_with_scheme(py, private_key, &scheme, data)
+}
+
 fn py_hash_name_from_hash_type(hash_type: HashType) -> Option<&'static str> {
     match hash_type {
         HashType::None => None,
+        HashType::Sha1 => Some("SHA1"),
         HashType::Sha224 => Some("SHA224"),
         HashType::Sha256 => Some("SHA256"),
         HashType::Sha384 => Some("SHA384"),
         HashType::Sha512 => Some("SHA512"),
+        HashType::Sha512_224 => Some("SHA512_224"),
+        HashType::Sha512_256 => Some("SHA512_256"),
         HashType::Sha3_224 => Some("SHA3_224"),
         HashType::Sha3_256 => Some("SHA3_256"),
         HashType::Sha3_384 => Some("SHA3_384"),
@@ -339,45 +449,36 @@ fn py_hash_name_from_hash_type(hash_type: HashType) -> Option<&'static str> {
     }
 }
 
-pub(crate) fn verify_signature_with_oid<'p>(
-    py: pyo3::Python<'p>,
-    issuer_public_key: &'p pyo3::PyAny,
-    signature_algorithm: &common::AlgorithmIdentifier<'_>,
+fn verify_with_scheme(
+    py: pyo3::Python<'_>,
+    issuer_public_key: &pyo3::PyAny,
+    scheme: &SignatureScheme,
     signature: &[u8],
     data: &[u8],
 ) -> CryptographyResult<()> {
-    let key_type = identify_public_key_type(py, issuer_public_key)?;
-    let (sig_key_type, sig_hash_type) =
-        identify_key_hash_type_for_algorithm_params(&signature_algorithm.params)?;
-    if key_type != sig_key_type {
-        return Err(CryptographyError::from(
-            pyo3::exceptions::PyValueError::new_err(
-                "Signature algorithm does not match issuer key type",
-            ),
-        ));
-    }
-    let sig_hash_name = py_hash_name_from_hash_type(sig_hash_type);
-    let hashes = py.import(pyo3::intern!(py, "cryptography.hazmat.primitives.hashes"))?;
-    let signature_hash = match sig_hash_name {
-        Some(data) => hashes.getattr(data)?.call0()?,
-        None => py.None().into_ref(py),
-    };
-
-    match key_type {
-        KeyType::Ed25519 | KeyType::Ed448 => {
-            issuer_public_key.call_method1(pyo3::intern!(py, "verify"), (signature, data))?
+    match scheme {
+        SignatureScheme::Ed25519 | SignatureScheme::Ed448 => {
+            issuer_public_key.call_method1(pyo3::intern!(py, "verify"), (signature, data))?;
         }
-        KeyType::Ec => {
+        SignatureScheme::Ec(hash_type) => {
+            let hash_obj = py_hash_object_for_type(py, *hash_type)?;
             let ec_mod = py.import(pyo3::intern!(
                 py,
                 "cryptography.hazmat.primitives.asymmetric.ec"
             ))?;
             let ecdsa = ec_mod
                 .getattr(pyo3::intern!(py, "ECDSA"))?
-                .call1((signature_hash,))?;
-            issuer_public_key.call_method1(pyo3::intern!(py, "verify"), (signature, data, ecdsa))?
+                .call1((hash_obj,))?;
+            issuer_public_key
+                .call_method1(pyo3::intern!(py, "verify"), (signature, data, ecdsa))?;
         }
-        KeyType::Rsa => {
+        SignatureScheme::Dsa(hash_type) => {
+            let hash_obj = py_hash_object_for_type(py, *hash_type)?;
+            issuer_public_key
+                .call_method1(pyo3::intern!(py, "verify"), (signature, data, hash_obj))?;
+        }
+        SignatureScheme::RsaPkcs1v15(hash_type) => {
+            let hash_obj = py_hash_object_for_type(py, *hash_type)?;
             let padding_mod = py.import(pyo3::intern!(
                 py,
                 "cryptography.hazmat.primitives.asymmetric.padding"
@@ -387,66 +488,90 @@ pub(crate) fn verify_signature_with_oid<'p>(
                 .call0()?;
             issuer_public_key.call_method1(
                 pyo3::intern!(py, "verify"),
-                (signature, data, pkcs1v15, signature_hash),
-            )?
+                (signature, data, pkcs1v15, hash_obj),
+            )?;
+        }
+        // RSASSA-PSS carries its own mask-generation hash and salt length in
+        // the algorithm parameters, rather than reusing PKCS1v15's implicit
+        // defaults, so it needs its own padding object.
+        SignatureScheme::RsaPss {
+            hash,
+            mgf_hash,
+            salt_length,
+        } => {
+            let hash_obj = py_hash_object_for_type(py, *hash)?;
+            let mgf_hash_obj = py_hash_object_for_type(py, *mgf_hash)?;
+            let padding_mod = py.import(pyo3::intern!(
+                py,
+                "cryptography.hazmat.primitives.asymmetric.padding"
+            ))?;
+            let mgf1 = padding_mod
+                .getattr(pyo3::intern!(py, "MGF1"))?
+                .call1((mgf_hash_obj,))?;
+            let pss = padding_mod
+                .getattr(pyo3::intern!(py, "PSS"))?
+                .call1((mgf1, *salt_length))?;
+            issuer_public_key.call_method1(
+                pyo3::intern!(py, "verify"),
+                (signature, data, pss, hash_obj),
+            )?;
         }
-        KeyType::Dsa => issuer_public_key.call_method1(
-            pyo3::intern!(py, "verify"),
-            (signature, data, signature_hash),
-        )?,
     };
     Ok(())
 }
 
+pub(crate) fn verify_signature_with_oid<'p>(
+    py: pyo3::Python<'p>,
+    issuer_public_key: &'p pyo3::PyAny,
+    signature_algorithm: &common::AlgorithmIdentifier<'_>,
+    signature: &[u8],
+    data: &[u8],
+) -> CryptographyResult<()> {
+    let key_type = identify_public_key_type(py, issuer_public_key)?;
+    let scheme = scheme_for_algorithm_params(&signature_algorithm.params)?;
+    if key_type != scheme.key_type() {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "Signature algorithm does not match issuer key type",
+            ),
+        ));
+    }
+    verify_with_scheme(py, issuer_public_key, &scheme, signature, data)
+}
+
 pub(crate) fn identify_public_key_type(
     py: pyo3::Python<'_>,
     public_key: &pyo3::PyAny,
 ) -> pyo3::PyResult<KeyType> {
-    let rsa_key_type: &pyo3::types::PyType = py
-        .import(pyo3::intern!(
-            py,
-            "cryptography.hazmat.primitives.asymmetric.rsa"
-        ))?
-        .getattr(pyo3::intern!(py, "RSAPublicKey"))?
-        .extract()?;
-    let dsa_key_type: &pyo3::types::PyType = py
-        .import(pyo3::intern!(
-            py,
-            "cryptography.hazmat.primitives.asymmetric.dsa"
-        ))?
-        .getattr(pyo3::intern!(py, "DSAPublicKey"))?
-        .extract()?;
-    let ec_key_type: &pyo3::types::PyType = py
-        .import(pyo3::intern!(
-            py,
-            "cryptography.hazmat.primitives.asymmetric.ec"
-        ))?
-        .getattr(pyo3::intern!(py, "EllipticCurvePublicKey"))?
-        .extract()?;
-    let ed25519_key_type: &pyo3::types::PyType = py
-        .import(pyo3::intern!(
-            py,
-            "cryptography.hazmat.primitives.asymmetric.ed25519"
-        ))?
-        .getattr(pyo3::intern!(py, "Ed25519PublicKey"))?
-        .extract()?;
-    let ed448_key_type: &pyo3::types::PyType = py
-        .import(pyo3::intern!(
-            py,
-            "cryptography.hazmat.primitives.asymmetric.ed448"
-        ))?
-        .getattr(pyo3::intern!(py, "Ed448PublicKey"))?
-        .extract()?;
-
-    if public_key.is_instance(rsa_key_type)? {
+    if public_key.is_instance(RSA_PUBLIC_KEY_TYPE.get(
+        py,
+        "cryptography.hazmat.primitives.asymmetric.rsa",
+        "RSAPublicKey",
+    )?)? {
         Ok(KeyType::Rsa)
-    } else if public_key.is_instance(dsa_key_type)? {
+    } else if public_key.is_instance(DSA_PUBLIC_KEY_TYPE.get(
+        py,
+        "cryptography.hazmat.primitives.asymmetric.dsa",
+        "DSAPublicKey",
+    )?)? {
         Ok(KeyType::Dsa)
-    } else if public_key.is_instance(ec_key_type)? {
+    } else if public_key.is_instance(EC_PUBLIC_KEY_TYPE.get(
+        py,
+        "cryptography.hazmat.primitives.asymmetric.ec",
+        "EllipticCurvePublicKey",
+    )?)? {
         Ok(KeyType::Ec)
-    } else if public_key.is_instance(ed25519_key_type)? {
+    } else if public_key.is_instance(ED25519_PUBLIC_KEY_TYPE.get(
+        py,
+        "cryptography.hazmat.primitives.asymmetric.ed25519",
+        "Ed25519PublicKey",
+    )?)? {
         Ok(KeyType::Ed25519)
-    } else if public_key.is_instance(ed448_key_type)? {
+    } else if public_key.is_instance(ED448_PUBLIC_KEY_TYPE.get(
+        py,
+        "cryptography.hazmat.primitives.asymmetric.ed448",
+        "Ed448PublicKey",
+    )?)? {
         Ok(KeyType::Ed448)
     } else {
         Err(pyo3::exceptions::PyTypeError::new_err(
@@ -455,32 +580,99 @@ pub(crate) fn identify_public_key_type(
     }
 }
 
-fn identify_key_hash_type_for_algorithm_params(
+fn scheme_for_algorithm_params(
     params: &common::AlgorithmParameters<'_>,
-) -> pyo3::PyResult<(KeyType, HashType)> {
+) -> pyo3::PyResult<SignatureScheme> {
     match params {
-        common::AlgorithmParameters::RsaWithSha224(..) => Ok((KeyType::Rsa, HashType::Sha224)),
-        common::AlgorithmParameters::RsaWithSha256(..) => Ok((KeyType::Rsa, HashType::Sha256)),
-        common::AlgorithmParameters::RsaWithSha384(..) => Ok((KeyType::Rsa, HashType::Sha384)),
-        common::AlgorithmParameters::RsaWithSha512(..) => Ok((KeyType::Rsa, HashType::Sha512)),
-        common::AlgorithmParameters::RsaWithSha3_224(..) => Ok((KeyType::Rsa, HashType::Sha3_224)),
-        common::AlgorithmParameters::RsaWithSha3_256(..) => Ok((KeyType::Rsa, HashType::Sha3_256)),
-        common::AlgorithmParameters::RsaWithSha3_384(..) => Ok((KeyType::Rsa, HashType::Sha3_384)),
-        common::AlgorithmParameters::RsaWithSha3_512(..) => Ok((KeyType::Rsa, HashType::Sha3_512)),
-        common::AlgorithmParameters::EcDsaWithSha224 => Ok((KeyType::Ec, HashType::Sha224)),
-        common::AlgorithmParameters::EcDsaWithSha256 => Ok((KeyType::Ec, HashType::Sha256)),
-        common::AlgorithmParameters::EcDsaWithSha384 => Ok((KeyType::Ec, HashType::Sha384)),
-        common::AlgorithmParameters::EcDsaWithSha512 => Ok((KeyType::Ec, HashType::Sha512)),
-        common::AlgorithmParameters::EcDsaWithSha3_224 => Ok((KeyType::Ec, HashType::Sha3_224)),
-        common::AlgorithmParameters::EcDsaWithSha3_256 => Ok((KeyType::Ec, HashType::Sha3_256)),
-        common::AlgorithmParameters::EcDsaWithSha3_384 => Ok((KeyType::Ec, HashType::Sha3_384)),
-        common::AlgorithmParameters::EcDsaWithSha3_512 => Ok((KeyType::Ec, HashType::Sha3_512)),
-        common::AlgorithmParameters::Ed25519 => Ok((KeyType::Ed25519, HashType::None)),
-        common::AlgorithmParameters::Ed448 => Ok((KeyType::Ed448, HashType::None)),
-        common::AlgorithmParameters::DsaWithSha224 => Ok((KeyType::Dsa, HashType::Sha224)),
-        common::AlgorithmParameters::DsaWithSha256 => Ok((KeyType::Dsa, HashType::Sha256)),
-        common::AlgorithmParameters::DsaWithSha384 => Ok((KeyType::Dsa, HashType::Sha384)),
-        common::AlgorithmParameters::DsaWithSha512 => Ok((KeyType::Dsa, HashType::Sha512)),
+        common::AlgorithmParameters::RsaWithSha1(..)
+        | common::AlgorithmParameters::RsaWithSha1Alt(..) => {
+            Ok(SignatureScheme::RsaPkcs1v15(HashType::Sha1))
+        }
+        common::AlgorithmParameters::RsaWithSha224(..) => {
+            Ok(SignatureScheme::RsaPkcs1v15(HashType::Sha224))
+        }
+        common::AlgorithmParameters::RsaWithSha256(..) => {
+            Ok(SignatureScheme::RsaPkcs1v15(HashType::Sha256))
+        }
+        common::AlgorithmParameters::RsaWithSha384(..) => {
+            Ok(SignatureScheme::RsaPkcs1v15(HashType::Sha384))
+        }
+        common::AlgorithmParameters::RsaWithSha512(..) => {
+            Ok(SignatureScheme::RsaPkcs1v15(HashType::Sha512))
+        }
+        common::AlgorithmParameters::RsaWithSha512_224(..) => {
+            Ok(SignatureScheme::RsaPkcs1v15(HashType::Sha512_224))
+        }
+        common::AlgorithmParameters::RsaWithSha512_256(..) => {
+            Ok(SignatureScheme::RsaPkcs1v15(HashType::Sha512_256))
+        }
+        common::AlgorithmParameters::RsaWithSha3_224(..) => {
+            Ok(SignatureScheme::RsaPkcs1v15(HashType::Sha3_224))
+        }
+        common::AlgorithmParameters::RsaWithSha3_256(..) => {
+            Ok(SignatureScheme::RsaPkcs1v15(HashType::Sha3_256))
+        }
+        common::AlgorithmParameters::RsaWithSha3_384(..) => {
+            Ok(SignatureScheme::RsaPkcs1v15(HashType::Sha3_384))
+        }
+        common::AlgorithmParameters::RsaWithSha3_512(..) => {
+            Ok(SignatureScheme::RsaPkcs1v15(HashType::Sha3_512))
+        }
+        common::AlgorithmParameters::EcDsaWithSha1(..) => Ok(SignatureScheme::Ec(HashType::Sha1)),
+        common::AlgorithmParameters::EcDsaWithSha224(..) => {
+            Ok(SignatureScheme::Ec(HashType::Sha224))
+        }
+        common::AlgorithmParameters::EcDsaWithSha256(..) => {
+            Ok(SignatureScheme::Ec(HashType::Sha256))
+        }
+        common::AlgorithmParameters::EcDsaWithSha384(..) => {
+            Ok(SignatureScheme::Ec(HashType::Sha384))
+        }
+        common::AlgorithmParameters::EcDsaWithSha512(..) => {
+            Ok(SignatureScheme::Ec(HashType::Sha512))
+        }
+        common::AlgorithmParameters::EcDsaWithSha512_224(..) => {
+            Ok(SignatureScheme::Ec(HashType::Sha512_224))
+        }
+        common::AlgorithmParameters::EcDsaWithSha512_256(..) => {
+            Ok(SignatureScheme::Ec(HashType::Sha512_256))
+        }
+        common::AlgorithmParameters::EcDsaWithSha3_224 => {
+            Ok(SignatureScheme::Ec(HashType::Sha3_224))
+        }
+        common::AlgorithmParameters::EcDsaWithSha3_256 => {
+            Ok(SignatureScheme::Ec(HashType::Sha3_256))
+        }
+        common::AlgorithmParameters::EcDsaWithSha3_384 => {
+            Ok(SignatureScheme::Ec(HashType::Sha3_384))
+        }
+        common::AlgorithmParameters::EcDsaWithSha3_512 => {
+            Ok(SignatureScheme::Ec(HashType::Sha3_512))
+        }
+        common::AlgorithmParameters::Ed25519 => Ok(SignatureScheme::Ed25519),
+        common::AlgorithmParameters::Ed448 => Ok(SignatureScheme::Ed448),
+        common::AlgorithmParameters::DsaWithSha1 => Ok(SignatureScheme::Dsa(HashType::Sha1)),
+        common::AlgorithmParameters::DsaWithSha224 => Ok(SignatureScheme::Dsa(HashType::Sha224)),
+        common::AlgorithmParameters::DsaWithSha256 => Ok(SignatureScheme::Dsa(HashType::Sha256)),
+        common::AlgorithmParameters::DsaWithSha384 => Ok(SignatureScheme::Dsa(HashType::Sha384)),
+        common::AlgorithmParameters::DsaWithSha512 => Ok(SignatureScheme::Dsa(HashType::Sha512)),
+        common::AlgorithmParameters::RsaPss(Some(pss)) => {
+            // The RFC 4055 §3.1 defaults for absent fields are filled in when
+            // `RsaPssParameters` is parsed; `as_verification_config` then
+            // derives the digest from the inner `hashAlgorithm` and rejects a
+            // `maskGenAlgorithm` whose inner digest disagrees with it, a
+            // trailer field other than 1, or a salt length other than the
+            // digest length.
+            let (hash_algorithm, salt_length) = pss.as_verification_config().ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err("Invalid RSASSA-PSS parameters")
+            })?;
+            let hash = hash_type_from_algorithm_params(&hash_algorithm)?;
+            Ok(SignatureScheme::RsaPss {
+                hash,
+                mgf_hash: hash,
+                salt_length,
+            })
+        }
         _ => Err(pyo3::exceptions::PyValueError::new_err(
             "Unsupported signature algorithm",
         )),
@@ -490,225 +682,348 @@ fn identify_key_hash_type_for_algorithm_params(
 fn identify_alg_params_for_hash_type(
     hash_type: HashType,
 ) -> pyo3::PyResult<common::AlgorithmParameters<'static>> {
+    // Always emit the explicit NULL form: RFC 4055 §2.1 treats NULL and
+    // absent parameters as equivalent, but an explicit NULL is the
+    // conventional encoding and keeps round-tripping stable.
     match hash_type {
-        HashType::Sha224 => Ok(common::AlgorithmParameters::Sha224(())),
-        HashType::Sha256 => Ok(common::AlgorithmParameters::Sha256(())),
-        HashType::Sha384 => Ok(common::AlgorithmParameters::Sha384(())),
-        HashType::Sha512 => Ok(common::AlgorithmParameters::Sha512(())),
-        HashType::Sha3_224 => Ok(common::AlgorithmParameters::Sha3_224(())),
-        HashType::Sha3_256 => Ok(common::AlgorithmParameters::Sha3_256(())),
-        HashType::Sha3_384 => Ok(common::AlgorithmParameters::Sha3_384(())),
-        HashType::Sha3_512 => Ok(common::AlgorithmParameters::Sha3_512(())),
+        HashType::Sha224 => Ok(common::AlgorithmParameters::Sha224(Some(()))),
+        HashType::Sha256 => Ok(common::AlgorithmParameters::Sha256(Some(()))),
+        HashType::Sha384 => Ok(common::AlgorithmParameters::Sha384(Some(()))),
+        HashType::Sha512 => Ok(common::AlgorithmParameters::Sha512(Some(()))),
+        HashType::Sha512_224 => Ok(common::AlgorithmParameters::Sha512_224(Some(()))),
+        HashType::Sha512_256 => Ok(common::AlgorithmParameters::Sha512_256(Some(()))),
+        HashType::Sha3_224 => Ok(common::AlgorithmParameters::Sha3_224(Some(()))),
+        HashType::Sha3_256 => Ok(common::AlgorithmParameters::Sha3_256(Some(()))),
+        HashType::Sha3_384 => Ok(common::AlgorithmParameters::Sha3_384(Some(()))),
+        HashType::Sha3_512 => Ok(common::AlgorithmParameters::Sha3_512(Some(()))),
+        HashType::Sha1 => Err(exceptions::UnsupportedAlgorithm::new_err(
+            "SHA1 may not be used when generating new signatures",
+        )),
         HashType::None => Err(pyo3::exceptions::PyTypeError::new_err(
             "Algorithm must be a registered hash algorithm, not None.",
         )),
     }
 }
 
+// RFC 4055 §2.1 requires NULL and absent parameters to be treated as
+// equivalent, so every arm here matches on the OID alone and ignores
+// whether the NULL was encoded explicitly or omitted.
+fn hash_type_from_algorithm_params(
+    params: &common::AlgorithmParameters<'_>,
+) -> pyo3::PyResult<HashType> {
+    match params {
+        common::AlgorithmParameters::Sha1(_) => Ok(HashType::Sha1),
+        common::AlgorithmParameters::Sha224(_) => Ok(HashType::Sha224),
+        common::AlgorithmParameters::Sha256(_) => Ok(HashType::Sha256),
+        common::AlgorithmParameters::Sha384(_) => Ok(HashType::Sha384),
+        common::AlgorithmParameters::Sha512(_) => Ok(HashType::Sha512),
+        common::AlgorithmParameters::Sha512_224(_) => Ok(HashType::Sha512_224),
+        common::AlgorithmParameters::Sha512_256(_) => Ok(HashType::Sha512_256),
+        common::AlgorithmParameters::Sha3_224(_) => Ok(HashType::Sha3_224),
+        common::AlgorithmParameters::Sha3_256(_) => Ok(HashType::Sha3_256),
+        common::AlgorithmParameters::Sha3_384(_) => Ok(HashType::Sha3_384),
+        common::AlgorithmParameters::Sha3_512(_) => Ok(HashType::Sha3_512),
+        _ => Err(exceptions::UnsupportedAlgorithm::new_err(
+            "Unsupported hash algorithm for RSASSA-PSS verification",
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        identify_alg_params_for_hash_type, identify_key_hash_type_for_algorithm_params,
-        py_hash_name_from_hash_type, HashType, KeyType,
+        identify_alg_params_for_hash_type, py_hash_name_from_hash_type, scheme_for_algorithm_params,
+        HashType, KeyType, SignatureScheme,
     };
     use cryptography_x509::{common, oid};
 
     #[test]
-    fn test_identify_key_hash_type_for_algorithm_params() {
+    fn test_scheme_for_algorithm_params() {
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::RsaWithSha224(Some(()))
-            )
-            .unwrap(),
-            (KeyType::Rsa, HashType::Sha224)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaWithSha224(Some(())))
+                .unwrap(),
+            SignatureScheme::RsaPkcs1v15(HashType::Sha224)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::RsaWithSha256(Some(()))
-            )
-            .unwrap(),
-            (KeyType::Rsa, HashType::Sha256)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaWithSha256(Some(())))
+                .unwrap(),
+            SignatureScheme::RsaPkcs1v15(HashType::Sha256)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::RsaWithSha384(Some(()))
-            )
-            .unwrap(),
-            (KeyType::Rsa, HashType::Sha384)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaWithSha384(Some(())))
+                .unwrap(),
+            SignatureScheme::RsaPkcs1v15(HashType::Sha384)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::RsaWithSha512(Some(()))
-            )
-            .unwrap(),
-            (KeyType::Rsa, HashType::Sha512)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaWithSha512(Some(())))
+                .unwrap(),
+            SignatureScheme::RsaPkcs1v15(HashType::Sha512)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::RsaWithSha3_224(Some(()))
-            )
-            .unwrap(),
-            (KeyType::Rsa, HashType::Sha3_224)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaWithSha512_224(Some(())))
+                .unwrap(),
+            SignatureScheme::RsaPkcs1v15(HashType::Sha512_224)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::RsaWithSha3_256(Some(()))
-            )
-            .unwrap(),
-            (KeyType::Rsa, HashType::Sha3_256)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaWithSha512_256(Some(())))
+                .unwrap(),
+            SignatureScheme::RsaPkcs1v15(HashType::Sha512_256)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::RsaWithSha3_384(Some(()))
-            )
-            .unwrap(),
-            (KeyType::Rsa, HashType::Sha3_384)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaWithSha3_224(Some(())))
+                .unwrap(),
+            SignatureScheme::RsaPkcs1v15(HashType::Sha3_224)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::RsaWithSha3_512(Some(()))
-            )
-            .unwrap(),
-            (KeyType::Rsa, HashType::Sha3_512)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaWithSha3_256(Some(())))
+                .unwrap(),
+            SignatureScheme::RsaPkcs1v15(HashType::Sha3_256)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::EcDsaWithSha224
-            )
-            .unwrap(),
-            (KeyType::Ec, HashType::Sha224)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaWithSha3_384(Some(())))
+                .unwrap(),
+            SignatureScheme::RsaPkcs1v15(HashType::Sha3_384)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::EcDsaWithSha256
-            )
-            .unwrap(),
-            (KeyType::Ec, HashType::Sha256)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaWithSha3_512(Some(())))
+                .unwrap(),
+            SignatureScheme::RsaPkcs1v15(HashType::Sha3_512)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::EcDsaWithSha384
-            )
-            .unwrap(),
-            (KeyType::Ec, HashType::Sha384)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::EcDsaWithSha224(Some(())))
+                .unwrap(),
+            SignatureScheme::Ec(HashType::Sha224)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::EcDsaWithSha512
-            )
-            .unwrap(),
-            (KeyType::Ec, HashType::Sha512)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::EcDsaWithSha256(Some(())))
+                .unwrap(),
+            SignatureScheme::Ec(HashType::Sha256)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::EcDsaWithSha3_224
-            )
-            .unwrap(),
-            (KeyType::Ec, HashType::Sha3_224)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::EcDsaWithSha384(Some(())))
+                .unwrap(),
+            SignatureScheme::Ec(HashType::Sha384)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::EcDsaWithSha3_256
-            )
-            .unwrap(),
-            (KeyType::Ec, HashType::Sha3_256)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::EcDsaWithSha512(Some(())))
+                .unwrap(),
+            SignatureScheme::Ec(HashType::Sha512)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::EcDsaWithSha3_384
-            )
+            scheme_for_algorithm_params(&common::AlgorithmParameters::EcDsaWithSha512_224(Some(
+                ()
+            )))
             .unwrap(),
-            (KeyType::Ec, HashType::Sha3_384)
+            SignatureScheme::Ec(HashType::Sha512_224)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::EcDsaWithSha3_512
-            )
+            scheme_for_algorithm_params(&common::AlgorithmParameters::EcDsaWithSha512_256(Some(
+                ()
+            )))
             .unwrap(),
-            (KeyType::Ec, HashType::Sha3_512)
+            SignatureScheme::Ec(HashType::Sha512_256)
+        );
+        assert_eq!(
+            scheme_for_algorithm_params(&common::AlgorithmParameters::EcDsaWithSha3_224).unwrap(),
+            SignatureScheme::Ec(HashType::Sha3_224)
+        );
+        assert_eq!(
+            scheme_for_algorithm_params(&common::AlgorithmParameters::EcDsaWithSha3_256).unwrap(),
+            SignatureScheme::Ec(HashType::Sha3_256)
+        );
+        assert_eq!(
+            scheme_for_algorithm_params(&common::AlgorithmParameters::EcDsaWithSha3_384).unwrap(),
+            SignatureScheme::Ec(HashType::Sha3_384)
+        );
+        assert_eq!(
+            scheme_for_algorithm_params(&common::AlgorithmParameters::EcDsaWithSha3_512).unwrap(),
+            SignatureScheme::Ec(HashType::Sha3_512)
+        );
+        assert_eq!(
+            scheme_for_algorithm_params(&common::AlgorithmParameters::Ed25519).unwrap(),
+            SignatureScheme::Ed25519
+        );
+        assert_eq!(
+            scheme_for_algorithm_params(&common::AlgorithmParameters::Ed448).unwrap(),
+            SignatureScheme::Ed448
+        );
+        assert_eq!(
+            scheme_for_algorithm_params(&common::AlgorithmParameters::DsaWithSha224).unwrap(),
+            SignatureScheme::Dsa(HashType::Sha224)
+        );
+        assert_eq!(
+            scheme_for_algorithm_params(&common::AlgorithmParameters::DsaWithSha256).unwrap(),
+            SignatureScheme::Dsa(HashType::Sha256)
+        );
+        assert_eq!(
+            scheme_for_algorithm_params(&common::AlgorithmParameters::DsaWithSha384).unwrap(),
+            SignatureScheme::Dsa(HashType::Sha384)
+        );
+        assert_eq!(
+            scheme_for_algorithm_params(&common::AlgorithmParameters::DsaWithSha512).unwrap(),
+            SignatureScheme::Dsa(HashType::Sha512)
         );
+        assert!(scheme_for_algorithm_params(&common::AlgorithmParameters::Other(
+            oid::TLS_FEATURE_OID,
+            None
+        ))
+        .is_err());
+
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(&common::AlgorithmParameters::Ed25519)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaWithSha1(Some(())))
                 .unwrap(),
-            (KeyType::Ed25519, HashType::None)
+            SignatureScheme::RsaPkcs1v15(HashType::Sha1)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(&common::AlgorithmParameters::Ed448)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaWithSha1Alt(Some(())))
                 .unwrap(),
-            (KeyType::Ed448, HashType::None)
+            SignatureScheme::RsaPkcs1v15(HashType::Sha1)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::DsaWithSha224
-            )
-            .unwrap(),
-            (KeyType::Dsa, HashType::Sha224)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::EcDsaWithSha1(Some(())))
+                .unwrap(),
+            SignatureScheme::Ec(HashType::Sha1)
         );
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::DsaWithSha256
-            )
-            .unwrap(),
-            (KeyType::Dsa, HashType::Sha256)
+            scheme_for_algorithm_params(&common::AlgorithmParameters::DsaWithSha1).unwrap(),
+            SignatureScheme::Dsa(HashType::Sha1)
         );
+
+        let sha256_hash_alg = common::AlgorithmIdentifier {
+            oid: asn1::DefinedByMarker::marker(),
+            params: common::AlgorithmParameters::Sha256(Some(())),
+        };
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::DsaWithSha384
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaPss(Some(Box::new(
+                common::RsaPssParameters {
+                    hash_algorithm: sha256_hash_alg.clone(),
+                    mask_gen_algorithm: common::MaskGenAlgorithm {
+                        oid: oid::MGF1_OID,
+                        params: sha256_hash_alg,
+                    },
+                    salt_length: 32,
+                    _trailer_field: 1,
+                }
+            )))
             )
             .unwrap(),
-            (KeyType::Dsa, HashType::Sha384)
+            SignatureScheme::RsaPss {
+                hash: HashType::Sha256,
+                mgf_hash: HashType::Sha256,
+                salt_length: 32,
+            }
         );
+        // LibreSSL-style PSS parameters: the MGF1 inner hash's NULL is
+        // omitted entirely rather than encoded explicitly, which RFC 4055
+        // §2.1 says must still be accepted.
+        let sha256_hash_alg_no_null = common::AlgorithmIdentifier {
+            oid: asn1::DefinedByMarker::marker(),
+            params: common::AlgorithmParameters::Sha256(None),
+        };
         assert_eq!(
-            identify_key_hash_type_for_algorithm_params(
-                &common::AlgorithmParameters::DsaWithSha512
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaPss(Some(Box::new(
+                common::RsaPssParameters {
+                    hash_algorithm: sha256_hash_alg_no_null.clone(),
+                    mask_gen_algorithm: common::MaskGenAlgorithm {
+                        oid: oid::MGF1_OID,
+                        params: sha256_hash_alg_no_null,
+                    },
+                    salt_length: 32,
+                    _trailer_field: 1,
+                }
+            )))
             )
             .unwrap(),
-            (KeyType::Dsa, HashType::Sha512)
+            SignatureScheme::RsaPss {
+                hash: HashType::Sha256,
+                mgf_hash: HashType::Sha256,
+                salt_length: 32,
+            }
         );
         assert!(
-            identify_key_hash_type_for_algorithm_params(&common::AlgorithmParameters::Other(
-                oid::TLS_FEATURE_OID,
-                None
-            ))
-            .is_err()
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaPss(None)).is_err()
+        );
+
+        // RFC 4055 §3.1 defaults (hashAlgorithm/maskGenAlgorithm/saltLength/
+        // trailerField all absent) resolve to SHA-1 with a 20-byte salt.
+        assert_eq!(
+            scheme_for_algorithm_params(&common::AlgorithmParameters::RsaPss(Some(Box::new(
+                common::RsaPssParameters {
+                    hash_algorithm: common::PSS_SHA1_HASH_ALG,
+                    mask_gen_algorithm: common::PSS_SHA1_MASK_GEN_ALG,
+                    salt_length: 20,
+                    _trailer_field: 1,
+                }
+            ))))
+            .unwrap(),
+            SignatureScheme::RsaPss {
+                hash: HashType::Sha1,
+                mgf_hash: HashType::Sha1,
+                salt_length: 20,
+            }
         );
     }
 
     #[test]
     fn test_identify_alg_params_for_hash_type() {
         for (hash, params) in [
-            (HashType::Sha224, common::AlgorithmParameters::Sha224(())),
-            (HashType::Sha256, common::AlgorithmParameters::Sha256(())),
-            (HashType::Sha384, common::AlgorithmParameters::Sha384(())),
-            (HashType::Sha512, common::AlgorithmParameters::Sha512(())),
+            (
+                HashType::Sha224,
+                common::AlgorithmParameters::Sha224(Some(())),
+            ),
+            (
+                HashType::Sha256,
+                common::AlgorithmParameters::Sha256(Some(())),
+            ),
+            (
+                HashType::Sha384,
+                common::AlgorithmParameters::Sha384(Some(())),
+            ),
+            (
+                HashType::Sha512,
+                common::AlgorithmParameters::Sha512(Some(())),
+            ),
+            (
+                HashType::Sha512_224,
+                common::AlgorithmParameters::Sha512_224(Some(())),
+            ),
+            (
+                HashType::Sha512_256,
+                common::AlgorithmParameters::Sha512_256(Some(())),
+            ),
             (
                 HashType::Sha3_224,
-                common::AlgorithmParameters::Sha3_224(()),
+                common::AlgorithmParameters::Sha3_224(Some(())),
             ),
             (
                 HashType::Sha3_256,
-                common::AlgorithmParameters::Sha3_256(()),
+                common::AlgorithmParameters::Sha3_256(Some(())),
             ),
             (
                 HashType::Sha3_384,
-                common::AlgorithmParameters::Sha3_384(()),
+                common::AlgorithmParameters::Sha3_384(Some(())),
             ),
             (
                 HashType::Sha3_512,
-                common::AlgorithmParameters::Sha3_512(()),
+                common::AlgorithmParameters::Sha3_512(Some(())),
             ),
         ] {
             assert_eq!(identify_alg_params_for_hash_type(hash).unwrap(), params);
         }
+
+        assert!(identify_alg_params_for_hash_type(HashType::Sha1).is_err());
     }
 
     #[test]
     fn test_py_hash_name_from_hash_type() {
         for (hash, name) in [
+            (HashType::Sha1, "SHA1"),
             (HashType::Sha224, "SHA224"),
             (HashType::Sha256, "SHA256"),
             (HashType::Sha384, "SHA384"),
             (HashType::Sha512, "SHA512"),
+            (HashType::Sha512_224, "SHA512_224"),
+            (HashType::Sha512_256, "SHA512_256"),
             (HashType::Sha3_224, "SHA3_224"),
             (HashType::Sha3_256, "SHA3_256"),
             (HashType::Sha3_384, "SHA3_384"),
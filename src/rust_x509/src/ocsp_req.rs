@@ -1,5 +1,7 @@
 use crate::{common, extensions, name};
 use asn1;
+use cryptography_keepalive::KeepAlive;
+use sha1::Digest;
 
 #[derive(asn1::Asn1Read, asn1::Asn1Write)]
 pub struct TBSRequest<'a> {
@@ -32,6 +34,48 @@ pub struct CertID<'a> {
     pub serial_number: asn1::BigInt<'a>,
 }
 
+impl<'a> CertID<'a> {
+    /// Builds a `CertID` per RFC 6960 4.1.1, computing `issuer_name_hash` and
+    /// `issuer_key_hash` from the issuer's encoded `Name` and
+    /// `SubjectPublicKeyInfo` rather than requiring the caller to hash them.
+    ///
+    /// `issuer_name` must be the DER encoding of the issuer's `Name`, and
+    /// `issuer_spki` its parsed `SubjectPublicKeyInfo`. `hash_algorithm`
+    /// selects the digest (only SHA-1 and SHA-256 are supported, matching
+    /// what responders commonly accept). The resulting hashes are allocated
+    /// in `ka`, whose lifetime bounds the returned `CertID`.
+    ///
+    /// Returns `None` if `hash_algorithm` names an unsupported digest.
+    pub fn new(
+        ka: &'a KeepAlive,
+        hash_algorithm: common::AlgorithmIdentifier<'a>,
+        issuer_name: &[u8],
+        issuer_spki: &common::SubjectPublicKeyInfo<'_>,
+        serial_number: asn1::BigInt<'a>,
+    ) -> Option<Self> {
+        let issuer_name_hash = ka.add(hash_with(&hash_algorithm, issuer_name)?);
+        let issuer_key_hash = ka.add(hash_with(
+            &hash_algorithm,
+            issuer_spki.subject_public_key.as_bytes(),
+        )?);
+
+        Some(CertID {
+            hash_algorithm,
+            issuer_name_hash,
+            issuer_key_hash,
+            serial_number,
+        })
+    }
+}
+
+fn hash_with(hash_algorithm: &common::AlgorithmIdentifier<'_>, data: &[u8]) -> Option<Vec<u8>> {
+    match hash_algorithm.params {
+        common::AlgorithmParameters::Sha1(_) => Some(sha1::Sha1::digest(data).to_vec()),
+        common::AlgorithmParameters::Sha256(_) => Some(sha2::Sha256::digest(data).to_vec()),
+        _ => None,
+    }
+}
+
 #[derive(asn1::Asn1Read, asn1::Asn1Write)]
 pub struct OCSPRequest<'a> {
     pub tbs_request: TBSRequest<'a>,
@@ -41,3 +85,88 @@ pub struct OCSPRequest<'a> {
     #[explicit(0)]
     pub optional_signature: Option<asn1::Sequence<'a>>,
 }
+
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct OCSPResponse<'a> {
+    pub response_status: u8,
+    #[explicit(0)]
+    pub response_bytes: Option<ResponseBytes<'a>>,
+}
+
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct ResponseBytes<'a> {
+    pub response_type: asn1::ObjectIdentifier,
+    pub response: asn1::OctetStringEncoded<BasicOCSPResponse<'a>>,
+}
+
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct BasicOCSPResponse<'a> {
+    pub tbs_response_data: ResponseData<'a>,
+    pub signature_algorithm: common::AlgorithmIdentifier<'a>,
+    pub signature: asn1::BitString<'a>,
+    #[explicit(0)]
+    pub certs: Option<
+        common::Asn1ReadableOrWritable<
+            'a,
+            asn1::SequenceOf<'a, asn1::Tlv<'a>>,
+            asn1::SequenceOfWriter<'a, asn1::Tlv<'a>>,
+        >,
+    >,
+}
+
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct ResponseData<'a> {
+    #[explicit(0)]
+    #[default(0)]
+    pub version: u8,
+    pub responder_id: ResponderID<'a>,
+    pub produced_at: asn1::GeneralizedTime,
+    pub responses: common::Asn1ReadableOrWritable<
+        'a,
+        asn1::SequenceOf<'a, SingleResponse<'a>>,
+        asn1::SequenceOfWriter<'a, SingleResponse<'a>>,
+    >,
+    #[explicit(1)]
+    pub response_extensions: Option<extensions::Extensions<'a>>,
+}
+
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub enum ResponderID<'a> {
+    #[explicit(1)]
+    ByName(name::Name<'a>),
+    #[explicit(2)]
+    ByKey(&'a [u8]),
+}
+
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct SingleResponse<'a> {
+    pub cert_id: CertID<'a>,
+    pub cert_status: CertStatus,
+    pub this_update: asn1::GeneralizedTime,
+    #[explicit(0)]
+    pub next_update: Option<asn1::GeneralizedTime>,
+    #[explicit(1)]
+    pub single_extensions: Option<extensions::Extensions<'a>>,
+}
+
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub enum CertStatus {
+    #[implicit(0)]
+    Good(asn1::Null),
+    #[implicit(1)]
+    Revoked(RevokedInfo),
+    #[implicit(2)]
+    Unknown(asn1::Null),
+}
+
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct RevokedInfo {
+    pub revocation_time: asn1::GeneralizedTime,
+    #[explicit(0)]
+    pub revocation_reason: Option<CRLReason>,
+}
+
+// CRLReason ::= ENUMERATED, as defined in RFC 5280 5.3.1. Defined locally
+// here since this crate doesn't otherwise have CRL support.
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct CRLReason(asn1::Enumerated);